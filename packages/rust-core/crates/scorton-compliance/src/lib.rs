@@ -4,6 +4,7 @@ use anyhow::Result;
 
 pub mod dora;
 pub mod nis2;
+pub mod attestation;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComplianceReport {
@@ -177,6 +178,18 @@ impl ComplianceEngine {
         })
     }
 
+    /// Runs the assessment and signs the resulting report with a detached
+    /// Schnorr signature, so regulators get a verifiable chain of custody
+    /// without trusting the server that produced it.
+    pub async fn run_and_sign_assessment(
+        &self,
+        target: &str,
+        signing_key: &k256::schnorr::SigningKey,
+    ) -> Result<attestation::AttestedReport> {
+        let report = self.run_compliance_assessment(target).await?;
+        attestation::sign_report(report, signing_key)
+    }
+
     fn convert_dora_findings(&self, dora_result: &dora::DORAAssessment) -> Vec<Finding> {
         let mut findings = Vec::new();
         
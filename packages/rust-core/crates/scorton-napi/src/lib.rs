@@ -255,7 +255,10 @@ pub async fn calculate_cyber_score(target: String) -> napi::Result<CyberScore> {
 
 #[napi]
 pub async fn assess_dora_compliance(target: String) -> napi::Result<DORAResult> {
-    match scorton_compliance::dora::assess_dora_compliance(&target).await {
+    let assessor = scorton_security::compliance::ComplianceAssessor::new(
+        scorton_security::compliance::ComplianceConfig::default(),
+    );
+    match assessor.assess_dora_compliance(&target).await {
         Ok(assessment) => Ok(DORAResult::new(
             assessment.ict_risk_score,
             assessment.incident_response_time.as_secs_f64() / 3600.0, // Convert to hours
@@ -269,7 +272,10 @@ pub async fn assess_dora_compliance(target: String) -> napi::Result<DORAResult>
 
 #[napi]
 pub async fn assess_nis2_compliance(target: String) -> napi::Result<NIS2Result> {
-    match scorton_compliance::nis2::assess_nis2_compliance(&target).await {
+    let assessor = scorton_security::compliance::ComplianceAssessor::new(
+        scorton_security::compliance::ComplianceConfig::default(),
+    );
+    match assessor.assess_nis2_compliance(&target).await {
         Ok(assessment) => Ok(NIS2Result::new(
             format!("{:?}", assessment.risk_level),
             assessment.incident_handling.reporting_time.as_secs_f64() / 3600.0, // Convert to hours
@@ -313,6 +319,59 @@ pub async fn run_comprehensive_scan(target: String) -> napi::Result<ScanResult>
     }
 }
 
+/// Same as [`run_comprehensive_scan`], but invokes `callback` with a JSON
+/// frame (`{phase, target, tool, status, payload}`) every time a tool
+/// starts or finishes, so Node consumers get the same progress stream the
+/// server's WebSocket endpoint exposes.
+#[napi]
+pub async fn run_comprehensive_scan_streaming(
+    target: String,
+    callback: napi::threadsafe_function::ThreadsafeFunction<String, napi::threadsafe_function::ErrorStrategy::CalleeHandled>,
+) -> napi::Result<ScanResult> {
+    let start_time = std::time::Instant::now();
+
+    let orchestrator = scorton_security::scanner::ScannerOrchestrator::new(
+        scorton_security::scanner::ScannerConfig::default(),
+    );
+
+    let (sender, mut receiver) = tokio::sync::broadcast::channel(32);
+    let forward_callback = callback.clone();
+    let forwarder = tokio::spawn(async move {
+        while let Ok(event) = receiver.recv().await {
+            if let Ok(frame) = serde_json::to_string(&event) {
+                forward_callback.call(Ok(frame), napi::threadsafe_function::ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    });
+
+    let outcome = orchestrator.run_comprehensive_scan_streaming(&target, sender).await;
+    // The sender was dropped with the scan, so the forwarder ends once it
+    // has delivered every queued event.
+    let _ = forwarder.await;
+
+    match outcome {
+        Ok(results) => {
+            let data = serde_json::to_string(&results).map_err(|e| napi::Error::from_reason(e.to_string()))?;
+            Ok(ScanResult::new(
+                target,
+                "comprehensive".to_string(),
+                "success".to_string(),
+                data,
+                start_time.elapsed().as_millis() as f64,
+                chrono::Utc::now().to_rfc3339(),
+            ))
+        }
+        Err(e) => Ok(ScanResult::new(
+            target,
+            "comprehensive".to_string(),
+            "error".to_string(),
+            format!("{{\"error\": \"{}\"}}", e),
+            start_time.elapsed().as_millis() as f64,
+            chrono::Utc::now().to_rfc3339(),
+        )),
+    }
+}
+
 #[napi]
 pub fn start_rust_server(config: String) -> napi::Result<()> {
     // This is a placeholder implementation
@@ -2,6 +2,12 @@ use serde::{Deserialize, Serialize};
 use anyhow::{Result, Context};
 use std::time::Duration;
 
+use crate::store::ComplianceStore;
+use crate::policy::{
+    self, AssessmentContext, BusinessContinuityPolicy, CompliancePolicy, IncidentReportingTimePolicy,
+    IncidentResponseTimePolicy, ResiliencePolicy, SupplyChainPolicy, ThirdPartyRiskPolicy,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DORAAssessment {
     pub ict_risk_score: f64,
@@ -36,7 +42,7 @@ pub enum Criticality {
     Critical,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ComplianceStatus {
     Compliant,
     PartiallyCompliant,
@@ -82,12 +88,60 @@ pub struct SupplyChainScore {
 
 pub struct ComplianceAssessor {
     config: ComplianceConfig,
+    metrics: Option<std::sync::Arc<crate::metrics::ComplianceMetrics>>,
+    provenance: Option<std::sync::Arc<crate::provenance::ProvenanceLog>>,
+    store: Option<std::sync::Arc<dyn crate::store::ComplianceStore<crate::store::AssessmentKey, AssessmentRecord> + Send + Sync>>,
+}
+
+/// Either framework's assessment, so both can share one
+/// `ComplianceStore<AssessmentKey, _>` keyed by `AssessmentFramework`
+/// rather than needing two separately-typed stores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AssessmentRecord {
+    Dora(DORAAssessment),
+    Nis2(NIS2Assessment),
 }
 
 #[derive(Debug, Clone)]
 pub struct ComplianceConfig {
     pub dora_thresholds: DORAThresholds,
     pub nis2_thresholds: NIS2Thresholds,
+    pub mode: AssessmentMode,
+    /// Re-run cadence used when `mode` is [`AssessmentMode::Continuous`].
+    pub continuous_interval: Duration,
+}
+
+/// How an assessment is run, modeled on a sealing-mode style enum so a
+/// single `ComplianceAssessor` entry point can serve every use case
+/// instead of exposing separate one-shot/scheduled/deep-scan methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssessmentMode {
+    /// Run the current single-pass assessment and return.
+    OneShot,
+    /// Re-run on `continuous_interval`, emitting each assessment on a
+    /// broadcast channel via [`ComplianceAssessor::assess_dora_continuous`].
+    Continuous,
+    /// Use the heavier, real analysis paths in `calculate_ict_risk_score`/
+    /// `assess_third_party_risks` instead of the fast placeholder
+    /// heuristics, trading speed for scan depth.
+    DeepScan,
+}
+
+impl Default for AssessmentMode {
+    fn default() -> Self {
+        AssessmentMode::OneShot
+    }
+}
+
+impl AssessmentMode {
+    pub fn from_env_str(s: &str) -> Option<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "ONE_SHOT" | "ONESHOT" => Some(AssessmentMode::OneShot),
+            "CONTINUOUS" => Some(AssessmentMode::Continuous),
+            "DEEP_SCAN" | "DEEPSCAN" => Some(AssessmentMode::DeepScan),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -117,13 +171,146 @@ impl Default for ComplianceConfig {
                 min_bcp_score: 0.8,
                 min_supply_chain_score: 0.7,
             },
+            mode: AssessmentMode::OneShot,
+            continuous_interval: Duration::from_secs(3600),
         }
     }
 }
 
+impl ComplianceConfig {
+    /// Same fields as [`Default`], but validated for internal consistency:
+    /// an incident can't be reported before it's been responded to, so
+    /// the DORA response deadline must fit inside the NIS2 reporting
+    /// deadline. Prefer this over constructing the struct literal
+    /// directly whenever thresholds come from outside the binary (env
+    /// vars, a config file, an API request).
+    pub fn new(dora_thresholds: DORAThresholds, nis2_thresholds: NIS2Thresholds, mode: AssessmentMode) -> Result<Self> {
+        if dora_thresholds.max_incident_response_time > nis2_thresholds.max_incident_reporting_time {
+            anyhow::bail!(
+                "compliance threshold configuration is internally inconsistent: DORA max incident \
+                 response time {:?} exceeds NIS2 max incident reporting time {:?}",
+                dora_thresholds.max_incident_response_time,
+                nis2_thresholds.max_incident_reporting_time
+            );
+        }
+
+        Ok(Self {
+            dora_thresholds,
+            nis2_thresholds,
+            mode,
+            continuous_interval: Duration::from_secs(3600),
+        })
+    }
+
+    /// The DORA controls this config implies, as independently evaluable
+    /// policies. Built fresh from the thresholds on every call so edits
+    /// to `dora_thresholds` are picked up without re-registering anything.
+    fn dora_policies(&self) -> Vec<Box<dyn CompliancePolicy>> {
+        vec![
+            Box::new(IncidentResponseTimePolicy {
+                max_incident_response_time: self.dora_thresholds.max_incident_response_time,
+            }),
+            Box::new(ResiliencePolicy {
+                min_resilience_score: self.dora_thresholds.min_resilience_score,
+            }),
+            Box::new(ThirdPartyRiskPolicy {
+                max_risk: self.dora_thresholds.max_third_party_risk.clone(),
+            }),
+        ]
+    }
+
+    /// The NIS2 controls this config implies, as independently evaluable
+    /// policies.
+    fn nis2_policies(&self) -> Vec<Box<dyn CompliancePolicy>> {
+        vec![
+            Box::new(IncidentReportingTimePolicy {
+                max_incident_reporting_time: self.nis2_thresholds.max_incident_reporting_time,
+            }),
+            Box::new(BusinessContinuityPolicy {
+                min_bcp_score: self.nis2_thresholds.min_bcp_score,
+            }),
+            Box::new(SupplyChainPolicy {
+                min_supply_chain_score: self.nis2_thresholds.min_supply_chain_score,
+            }),
+        ]
+    }
+}
+
 impl ComplianceAssessor {
     pub fn new(config: ComplianceConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            metrics: None,
+            provenance: None,
+            store: None,
+        }
+    }
+
+    /// Same as [`Self::new`], but publishes every assessment's numeric
+    /// outputs as Prometheus gauges/histograms through `metrics`, so
+    /// assessments run on a schedule can be scraped and alerted on over
+    /// time rather than only returned once from the async call.
+    pub fn with_metrics(config: ComplianceConfig, metrics: std::sync::Arc<crate::metrics::ComplianceMetrics>) -> Self {
+        Self {
+            config,
+            metrics: Some(metrics),
+            provenance: None,
+            store: None,
+        }
+    }
+
+    /// Same as [`Self::new`], but every assessment can be appended to a
+    /// signed, tamper-evident provenance log via [`Self::sign_and_append`].
+    pub fn with_provenance(config: ComplianceConfig, provenance: std::sync::Arc<crate::provenance::ProvenanceLog>) -> Self {
+        Self {
+            config,
+            metrics: None,
+            provenance: Some(provenance),
+            store: None,
+        }
+    }
+
+    /// Same as [`Self::new`], but every `DORAAssessment`/`NIS2Assessment`
+    /// this assessor produces is written to `store` (keyed by target,
+    /// framework, and the time the assessment ran), so trend queries via
+    /// `ComplianceStore::history` see every run instead of only the most
+    /// recent one returned from the async call.
+    pub fn with_store(
+        config: ComplianceConfig,
+        store: std::sync::Arc<dyn crate::store::ComplianceStore<crate::store::AssessmentKey, AssessmentRecord> + Send + Sync>,
+    ) -> Self {
+        Self {
+            config,
+            metrics: None,
+            provenance: None,
+            store: Some(store),
+        }
+    }
+
+    /// Signs `assessment` as an activity performed by `agent` against
+    /// `entity` and appends it to this assessor's provenance log.
+    /// Returns an error if no log was configured via [`Self::with_provenance`].
+    pub fn sign_and_append(
+        &self,
+        agent: &str,
+        entity: &str,
+        assessment: crate::provenance::AssessmentPayload,
+    ) -> Result<crate::provenance::Activity> {
+        let provenance = self
+            .provenance
+            .as_ref()
+            .context("no provenance log configured; construct with ComplianceAssessor::with_provenance")?;
+        provenance.sign_and_append(agent, entity, assessment)
+    }
+
+    /// Verifies this assessor's provenance log, returning the first
+    /// broken link if the chain has been tampered with.
+    pub fn verify_chain(&self) -> Result<crate::provenance::ChainVerification> {
+        let provenance = self
+            .provenance
+            .as_ref()
+            .context("no provenance log configured; construct with ComplianceAssessor::with_provenance")?;
+        Ok(provenance.verify_chain())
     }
 
     pub async fn assess_dora_compliance(&self, target: &str) -> Result<DORAAssessment> {
@@ -133,7 +320,7 @@ impl ComplianceAssessor {
         let incident_response_time = self.measure_incident_response_time(target).await?;
         let third_party_risks = self.assess_third_party_risks(target).await?;
         let resilience_score = self.calculate_resilience_score(target).await?;
-        
+
         let compliance_status = self.determine_dora_compliance_status(
             &ict_risk_score,
             &incident_response_time,
@@ -143,14 +330,66 @@ impl ComplianceAssessor {
 
         let recommendations = self.generate_dora_recommendations(&compliance_status);
 
-        Ok(DORAAssessment {
+        let assessment = DORAAssessment {
             ict_risk_score,
             incident_response_time,
             third_party_risks,
             resilience_score,
             compliance_status,
             recommendations,
-        })
+        };
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_dora(target, &assessment);
+        }
+
+        if let Some(store) = &self.store {
+            let key = crate::store::AssessmentKey {
+                target: target.to_string(),
+                framework: crate::store::AssessmentFramework::Dora,
+                timestamp: chrono::Utc::now().timestamp(),
+            };
+            store
+                .write(key, AssessmentRecord::Dora(assessment.clone()))
+                .context("failed to persist DORA assessment")?;
+        }
+
+        Ok(assessment)
+    }
+
+    /// Runs [`Self::assess_dora_compliance`] once if `mode` is
+    /// [`AssessmentMode::OneShot`]/[`AssessmentMode::DeepScan`], or
+    /// repeatedly on `continuous_interval` if `mode` is
+    /// [`AssessmentMode::Continuous`], broadcasting each result on the
+    /// returned channel so callers can feed it straight into
+    /// `ComplianceMetrics`/`CachedComplianceStore`.
+    pub fn assess_dora_continuous(
+        self: std::sync::Arc<Self>,
+        target: String,
+    ) -> tokio::sync::broadcast::Receiver<Result<DORAAssessment, String>> {
+        let (tx, rx) = tokio::sync::broadcast::channel(16);
+        let interval = self.config.continuous_interval;
+        let mode = self.config.mode;
+
+        tokio::spawn(async move {
+            loop {
+                let result = self
+                    .assess_dora_compliance(&target)
+                    .await
+                    .map_err(|e| e.to_string());
+                if tx.send(result).is_err() {
+                    // No receivers left; stop re-running.
+                    break;
+                }
+
+                if mode != AssessmentMode::Continuous {
+                    break;
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+
+        rx
     }
 
     pub async fn assess_nis2_compliance(&self, target: &str) -> Result<NIS2Assessment> {
@@ -168,24 +407,53 @@ impl ComplianceAssessor {
 
         let recommendations = self.generate_nis2_recommendations(&compliance_status);
 
-        Ok(NIS2Assessment {
+        let assessment = NIS2Assessment {
             risk_level,
             incident_handling,
             business_continuity,
             supply_chain_security,
             compliance_status,
             recommendations,
-        })
+        };
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_nis2(target, &assessment);
+        }
+
+        if let Some(store) = &self.store {
+            let key = crate::store::AssessmentKey {
+                target: target.to_string(),
+                framework: crate::store::AssessmentFramework::Nis2,
+                timestamp: chrono::Utc::now().timestamp(),
+            };
+            store
+                .write(key, AssessmentRecord::Nis2(assessment.clone()))
+                .context("failed to persist NIS2 assessment")?;
+        }
+
+        Ok(assessment)
     }
 
-    async fn calculate_ict_risk_score(&self, _target: &str) -> Result<f64> {
-        // Placeholder implementation
-        // In real implementation, this would analyze:
-        // - Infrastructure security
-        // - Network security
-        // - Application security
-        // - Data protection measures
-        Ok(0.75)
+    async fn calculate_ict_risk_score(&self, target: &str) -> Result<f64> {
+        if self.config.mode != AssessmentMode::DeepScan {
+            // Fast placeholder heuristic.
+            return Ok(0.75);
+        }
+
+        // DeepScan: derive the score from an actual TLS handshake instead
+        // of a fixed constant, penalizing each real vulnerability found.
+        let vulnerabilities = crate::ssl::check_ssl_vulnerabilities(target, 443).await?;
+        let penalty: f64 = vulnerabilities
+            .iter()
+            .map(|v| match v.severity {
+                crate::ssl::VulnerabilitySeverity::Critical => 0.3,
+                crate::ssl::VulnerabilitySeverity::High => 0.2,
+                crate::ssl::VulnerabilitySeverity::Medium => 0.1,
+                crate::ssl::VulnerabilitySeverity::Low => 0.05,
+            })
+            .sum();
+
+        Ok((1.0 - penalty).clamp(0.0, 1.0))
     }
 
     async fn measure_incident_response_time(&self, _target: &str) -> Result<Duration> {
@@ -194,17 +462,37 @@ impl ComplianceAssessor {
         Ok(Duration::from_secs(2 * 3600)) // 2 hours
     }
 
-    async fn assess_third_party_risks(&self, _target: &str) -> Result<Vec<ThirdPartyRisk>> {
-        // Placeholder implementation
-        // In real implementation, this would assess actual third-party vendors
-        Ok(vec![
-            ThirdPartyRisk {
+    async fn assess_third_party_risks(&self, target: &str) -> Result<Vec<ThirdPartyRisk>> {
+        if self.config.mode != AssessmentMode::DeepScan {
+            // Fast placeholder heuristic.
+            return Ok(vec![ThirdPartyRisk {
                 vendor_name: "Cloud Provider".to_string(),
                 risk_level: RiskLevel::Medium,
                 assessment_date: chrono::Utc::now(),
                 criticality: Criticality::High,
-            },
-        ])
+            }]);
+        }
+
+        // DeepScan: derive third-party dependencies from the target's
+        // actual DNS records (MX/NS hand-offs are real vendor exposure)
+        // instead of a single hardcoded vendor.
+        let records = crate::dns::enumerate_dns_records(target).await?;
+        let risks = records
+            .iter()
+            .filter(|record| record.record_type == "MX" || record.record_type == "NS")
+            .map(|record| ThirdPartyRisk {
+                vendor_name: record.value.clone(),
+                risk_level: RiskLevel::Medium,
+                assessment_date: chrono::Utc::now(),
+                criticality: if record.record_type == "MX" {
+                    Criticality::High
+                } else {
+                    Criticality::Medium
+                },
+            })
+            .collect();
+
+        Ok(risks)
     }
 
     async fn calculate_resilience_score(&self, _target: &str) -> Result<f64> {
@@ -257,19 +545,22 @@ impl ComplianceAssessor {
         third_party_risks: &[ThirdPartyRisk],
         resilience_score: &f64,
     ) -> ComplianceStatus {
-        let response_time_ok = *incident_response_time <= self.config.dora_thresholds.max_incident_response_time;
-        let resilience_ok = *resilience_score >= self.config.dora_thresholds.min_resilience_score;
-        let third_party_ok = !third_party_risks.iter().any(|risk| {
-            matches!(risk.risk_level, RiskLevel::High | RiskLevel::Critical)
-        });
-
-        if response_time_ok && resilience_ok && third_party_ok {
-            ComplianceStatus::Compliant
-        } else if response_time_ok || resilience_ok || third_party_ok {
-            ComplianceStatus::PartiallyCompliant
-        } else {
-            ComplianceStatus::NonCompliant
-        }
+        let ctx = AssessmentContext {
+            ict_risk_score: Some(*ict_risk_score),
+            incident_response_time: Some(*incident_response_time),
+            third_party_risks: Some(third_party_risks.to_vec()),
+            resilience_score: Some(*resilience_score),
+            ..Default::default()
+        };
+
+        let outcomes: Vec<_> = self
+            .config
+            .dora_policies()
+            .iter()
+            .map(|p| p.evaluate(&ctx))
+            .collect();
+
+        policy::aggregate(&outcomes)
     }
 
     fn determine_nis2_compliance_status(
@@ -279,17 +570,21 @@ impl ComplianceAssessor {
         business_continuity: &BCPStatus,
         supply_chain_security: &SupplyChainScore,
     ) -> ComplianceStatus {
-        let incident_ok = incident_handling.reporting_time <= self.config.nis2_thresholds.max_incident_reporting_time;
-        let bcp_ok = business_continuity.plan_exists && business_continuity.last_tested.is_some();
-        let supply_chain_ok = supply_chain_security.overall_score >= self.config.nis2_thresholds.min_supply_chain_score;
-
-        if incident_ok && bcp_ok && supply_chain_ok {
-            ComplianceStatus::Compliant
-        } else if incident_ok || bcp_ok || supply_chain_ok {
-            ComplianceStatus::PartiallyCompliant
-        } else {
-            ComplianceStatus::NonCompliant
-        }
+        let ctx = AssessmentContext {
+            incident_handling: Some(incident_handling.clone()),
+            business_continuity: Some(business_continuity.clone()),
+            supply_chain_security: Some(supply_chain_security.clone()),
+            ..Default::default()
+        };
+
+        let outcomes: Vec<_> = self
+            .config
+            .nis2_policies()
+            .iter()
+            .map(|p| p.evaluate(&ctx))
+            .collect();
+
+        policy::aggregate(&outcomes)
     }
 
     fn generate_dora_recommendations(&self, status: &ComplianceStatus) -> Vec<String> {
@@ -348,4 +643,27 @@ mod tests {
         let result = assessor.assess_nis2_compliance("example.com").await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_assessment_mode_from_env_str() {
+        assert_eq!(AssessmentMode::from_env_str("deep_scan"), Some(AssessmentMode::DeepScan));
+        assert_eq!(AssessmentMode::from_env_str("Continuous"), Some(AssessmentMode::Continuous));
+        assert_eq!(AssessmentMode::from_env_str("bogus"), None);
+    }
+
+    #[tokio::test]
+    async fn test_with_store_persists_every_assessment() {
+        let store = std::sync::Arc::new(crate::store::CachedComplianceStore::new(
+            crate::store::CacheUpdatePolicy::Overwrite,
+        ));
+        let assessor = ComplianceAssessor::with_store(ComplianceConfig::default(), store.clone());
+
+        assessor.assess_dora_compliance("example.com").await.unwrap();
+        assessor.assess_nis2_compliance("example.com").await.unwrap();
+
+        let history = store.history().unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(matches!(history[0].1, AssessmentRecord::Dora(_)));
+        assert!(matches!(history[1].1, AssessmentRecord::Nis2(_)));
+    }
 }
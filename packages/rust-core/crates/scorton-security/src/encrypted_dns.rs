@@ -0,0 +1,317 @@
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tokio_rustls::rustls::{self, pki_types::ServerName};
+use tokio_rustls::TlsConnector;
+
+/// Which encrypted-transport protocols a resolver was found to support,
+/// plus enough detail on each to audit the deployment (negotiated
+/// ALPN/TLS for DoT/DoH, provider identity for DNSCrypt).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedDnsSupport {
+    pub dot: Option<TlsProbeResult>,
+    pub doh: Option<TlsProbeResult>,
+    pub dnscrypt: Option<DnsCryptProbeResult>,
+}
+
+/// What a TLS-based probe (DoT on 853, DoH's underlying HTTPS on 443)
+/// observed during the handshake.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsProbeResult {
+    pub tls_version: String,
+    pub alpn_protocol: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsCryptProbeResult {
+    pub stamp: DnsStamp,
+    /// Populated if the provider's certificate was successfully fetched
+    /// and structurally parsed (magic/version/serial/validity window).
+    /// Signature verification against the provider public key in the
+    /// stamp is a documented follow-up, not performed here.
+    pub certificate: Option<DnsCryptCertificate>,
+}
+
+/// A decoded `sdns://` DNS stamp (draft-dnscrypt-dnsstamps). Only the
+/// DNSCrypt stamp type is decoded in full; other stamp types (DoH, DoT,
+/// plain resolver) parse just the protocol byte and address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsStamp {
+    pub protocol: StampProtocol,
+    pub properties: u64,
+    pub address: String,
+    /// Provider's Ed25519 public key, for DNSCrypt stamps.
+    pub provider_public_key: Option<String>,
+    pub provider_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StampProtocol {
+    DnsCrypt,
+    DoH,
+    DoT,
+    PlainDns,
+    Unknown(u8),
+}
+
+impl From<u8> for StampProtocol {
+    fn from(byte: u8) -> Self {
+        match byte {
+            0x00 => StampProtocol::PlainDns,
+            0x01 => StampProtocol::DnsCrypt,
+            0x02 => StampProtocol::DoH,
+            0x03 => StampProtocol::DoT,
+            other => StampProtocol::Unknown(other),
+        }
+    }
+}
+
+/// The structural fields of a DNSCrypt certificate, fetched as the TXT
+/// record at `2.dnscrypt-cert.<provider-name>` per the DNSCrypt protocol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsCryptCertificate {
+    pub es_version: u16,
+    pub signature: String,
+    pub resolver_public_key: String,
+    pub client_magic: String,
+    pub serial: u32,
+    pub ts_start: u32,
+    pub ts_end: u32,
+}
+
+/// Parses an `sdns://` DNS stamp into its component fields: a protocol
+/// byte, a properties bitfield, the resolver address, and (for DNSCrypt)
+/// the provider's public key and name.
+pub fn parse_dns_stamp(stamp: &str) -> Result<DnsStamp> {
+    let encoded = stamp
+        .strip_prefix("sdns://")
+        .context("DNS stamp must start with sdns://")?;
+
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(encoded)
+        .context("DNS stamp is not valid base64url")?;
+
+    let mut cursor = StampCursor::new(&bytes);
+
+    let protocol = StampProtocol::from(cursor.read_u8()?);
+    let properties = cursor.read_u64_le()?;
+
+    match protocol {
+        StampProtocol::DnsCrypt => {
+            let address = cursor.read_lp_string()?;
+            let provider_public_key = cursor.read_lp_bytes()?;
+            let provider_name = cursor.read_lp_string()?;
+
+            Ok(DnsStamp {
+                protocol,
+                properties,
+                address,
+                provider_public_key: Some(hex::encode(provider_public_key)),
+                provider_name: Some(provider_name),
+            })
+        }
+        _ => {
+            // DoH/DoT/plain stamps: address, then hashes/hostname/path
+            // that aren't needed for a support probe.
+            let address = cursor.read_lp_string().unwrap_or_default();
+            Ok(DnsStamp {
+                protocol,
+                properties,
+                address,
+                provider_public_key: None,
+                provider_name: None,
+            })
+        }
+    }
+}
+
+/// Minimal cursor over a DNS stamp's decoded bytes: a little-endian u64
+/// properties field followed by a sequence of length-prefixed (LP)
+/// byte strings, per the dnsstamps spec.
+struct StampCursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> StampCursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        let byte = *self.bytes.get(self.pos).context("DNS stamp truncated")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_u64_le(&mut self) -> Result<u64> {
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos + 8)
+            .context("DNS stamp truncated reading properties")?;
+        self.pos += 8;
+        Ok(u64::from_le_bytes(slice.try_into().unwrap()))
+    }
+
+    fn read_lp_bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_u8()? as usize;
+        let slice = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .context("DNS stamp truncated reading length-prefixed field")?;
+        self.pos += len;
+        Ok(slice.to_vec())
+    }
+
+    fn read_lp_string(&mut self) -> Result<String> {
+        let bytes = self.read_lp_bytes()?;
+        String::from_utf8(bytes).context("DNS stamp field is not valid UTF-8")
+    }
+}
+
+/// Probes `target:853` for DNS-over-TLS support via a real TLS handshake.
+pub async fn probe_dot(target: &str) -> Result<TlsProbeResult> {
+    probe_tls(target, 853, &[]).await
+}
+
+/// Probes `target` for real DNS-over-HTTPS support: a TLS handshake
+/// confirms the ALPN a DoH deployment would offer, then an actual RFC
+/// 8484 query against `https://target/dns-query` confirms the endpoint
+/// answers as a resolver rather than just terminating TLS. A plain HTTPS
+/// server with no DoH support will pass the handshake but fail the query.
+pub async fn probe_doh(target: &str) -> Result<TlsProbeResult> {
+    let tls = probe_tls(target, 443, &[b"h2".to_vec(), b"http/1.1".to_vec()]).await?;
+
+    let endpoint = format!("https://{}/dns-query", target);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .context("Failed to create DoH HTTP client")?;
+
+    crate::dns::doh_query(&client, &endpoint, "example.com", crate::dns::RecordType::Ns, 0)
+        .await
+        .context("Endpoint did not answer a DoH query at /dns-query")?;
+
+    Ok(tls)
+}
+
+async fn probe_tls(target: &str, port: u16, alpn_protocols: &[Vec<u8>]) -> Result<TlsProbeResult> {
+    let socket_addr = format!("{}:{}", target, port);
+    let stream = timeout(Duration::from_secs(10), TcpStream::connect(&socket_addr))
+        .await
+        .context("Connection timeout")?
+        .context("Failed to connect")?;
+
+    let mut root_store = rustls::RootCertStore::empty();
+    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    let mut config = rustls::ClientConfig::builder()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    config.alpn_protocols = alpn_protocols.to_vec();
+    let connector = TlsConnector::from(Arc::new(config));
+
+    let server_name =
+        ServerName::try_from(target.to_string()).map_err(|_| anyhow!("Invalid DNS name: {}", target))?;
+
+    let tls_stream = timeout(Duration::from_secs(10), connector.connect(server_name, stream))
+        .await
+        .context("TLS handshake timeout")?
+        .context("TLS handshake failed")?;
+
+    let (_, connection) = tls_stream.get_ref();
+    Ok(TlsProbeResult {
+        tls_version: format!("{:?}", connection.protocol_version()),
+        alpn_protocol: connection
+            .alpn_protocol()
+            .map(|p| String::from_utf8_lossy(p).to_string()),
+    })
+}
+
+/// Probes a DNSCrypt resolver described by `stamp_str`: parses the stamp,
+/// then fetches and structurally parses its certificate from
+/// `2.dnscrypt-cert.<provider-name>`.
+pub async fn probe_dnscrypt(stamp_str: &str) -> Result<DnsCryptProbeResult> {
+    let stamp = parse_dns_stamp(stamp_str)?;
+
+    let certificate = match &stamp.provider_name {
+        Some(provider_name) => fetch_dnscrypt_certificate(provider_name).await.ok(),
+        None => None,
+    };
+
+    Ok(DnsCryptProbeResult { stamp, certificate })
+}
+
+async fn fetch_dnscrypt_certificate(provider_name: &str) -> Result<DnsCryptCertificate> {
+    let query_name = format!("2.dnscrypt-cert.{}", provider_name.trim_end_matches('.'));
+
+    // The certificate is binary (signature/public-key bytes), so it's
+    // fetched as raw TXT rdata rather than through the lossy
+    // UTF-8 `DNSRecord.value: String` path the rest of dns.rs uses.
+    let records = crate::dns::fetch_txt_raw(&query_name, &crate::dns::ResolverConfig::default()).await?;
+
+    let raw = records
+        .first()
+        .context("No DNSCrypt certificate TXT record found")?;
+
+    parse_dnscrypt_certificate(raw)
+}
+
+/// Parses a DNSCrypt certificate's fixed-size fields out of its raw wire
+/// encoding (magic "DNSC", es-version, protocol-minor-version, signature,
+/// resolver public key, client magic, serial, and validity window).
+fn parse_dnscrypt_certificate(bytes: &[u8]) -> Result<DnsCryptCertificate> {
+    const MAGIC: &[u8] = b"DNSC";
+    if bytes.len() < 124 || &bytes[0..4] != MAGIC {
+        anyhow::bail!("not a recognizable DNSCrypt certificate");
+    }
+
+    let es_version = u16::from_be_bytes([bytes[4], bytes[5]]);
+    let signature = hex::encode(&bytes[8..72]);
+    let resolver_public_key = hex::encode(&bytes[72..104]);
+    let client_magic = hex::encode(&bytes[104..112]);
+    let serial = u32::from_be_bytes(bytes[112..116].try_into().unwrap());
+    let ts_start = u32::from_be_bytes(bytes[116..120].try_into().unwrap());
+    let ts_end = u32::from_be_bytes(bytes[120..124].try_into().unwrap());
+
+    Ok(DnsCryptCertificate {
+        es_version,
+        signature,
+        resolver_public_key,
+        client_magic,
+        serial,
+        ts_start,
+        ts_end,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_dns_stamp() {
+        // "sdns://AAcAAAAAAAAABzguOC44Ljg" decodes protocol 0x00 (plain),
+        // properties 7, address "8.8.8.8".
+        let stamp = parse_dns_stamp("sdns://AAcAAAAAAAAABzguOC44Ljg").unwrap();
+        assert_eq!(stamp.protocol, StampProtocol::PlainDns);
+        assert_eq!(stamp.address, "8.8.8.8");
+    }
+
+    #[test]
+    fn test_stamp_protocol_from_byte() {
+        assert_eq!(StampProtocol::from(0x01), StampProtocol::DnsCrypt);
+        assert_eq!(StampProtocol::from(0x02), StampProtocol::DoH);
+        assert_eq!(StampProtocol::from(0x03), StampProtocol::DoT);
+        assert_eq!(StampProtocol::from(0xFE), StampProtocol::Unknown(0xFE));
+    }
+
+    #[test]
+    fn test_parse_dnscrypt_certificate_rejects_short_input() {
+        assert!(parse_dnscrypt_certificate(b"too short").is_err());
+    }
+}
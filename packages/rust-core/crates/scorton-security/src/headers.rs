@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use anyhow::{Result, Context};
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 pub async fn analyze_security_headers(url: &str) -> Result<crate::SecurityHeaders> {
     let client = reqwest::Client::builder()
@@ -14,9 +16,13 @@ pub async fn analyze_security_headers(url: &str) -> Result<crate::SecurityHeader
         .await
         .context("Failed to send HTTP request")?;
 
-    let headers = response.headers();
-    
-    Ok(crate::SecurityHeaders {
+    Ok(security_headers_from(response.headers()))
+}
+
+/// Shared by [`analyze_security_headers`] and [`HeaderScanner::check`] so
+/// the two paths parse the response headers identically.
+fn security_headers_from(headers: &reqwest::header::HeaderMap) -> crate::SecurityHeaders {
+    crate::SecurityHeaders {
         strict_transport_security: headers
             .get("strict-transport-security")
             .and_then(|h| h.to_str().ok())
@@ -45,33 +51,387 @@ pub async fn analyze_security_headers(url: &str) -> Result<crate::SecurityHeader
             .get("permissions-policy")
             .and_then(|h| h.to_str().ok())
             .map(|s| s.to_string()),
-    })
+    }
 }
 
-pub async fn check_security_header_vulnerabilities(url: &str) -> Result<Vec<HeaderVulnerability>> {
+/// A cached [`analyze_security_headers`] result plus the conditional-
+/// request validators needed to revalidate it cheaply instead of
+/// re-downloading the full response.
+#[derive(Debug, Clone)]
+struct CachedHeaders {
+    headers: crate::SecurityHeaders,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: Instant,
+    /// From `Cache-Control: max-age=N`. `None` means the entry is never
+    /// served without revalidating against the origin first (e.g.
+    /// `no-cache`, or no freshness lifetime was given at all).
+    max_age: Option<Duration>,
+    /// Whether this entry is worth keeping around at all — an origin that
+    /// sends neither a validator nor a freshness lifetime gives us
+    /// nothing to reuse on the next call.
+    cacheable: bool,
+}
+
+impl CachedHeaders {
+    fn is_fresh(&self) -> bool {
+        self.max_age
+            .map(|max_age| self.fetched_at.elapsed() < max_age)
+            .unwrap_or(false)
+    }
+}
+
+/// Parsed `Cache-Control` response directives, covering just what
+/// [`HeaderScanner`] needs to decide cacheability and freshness.
+struct CacheControlDirectives {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<Duration>,
+}
+
+fn parse_cache_control(value: &str) -> CacheControlDirectives {
+    let mut directives = CacheControlDirectives {
+        no_store: false,
+        no_cache: false,
+        max_age: None,
+    };
+
+    for directive in value.split(',') {
+        let directive = directive.trim();
+        if directive.eq_ignore_ascii_case("no-store") {
+            directives.no_store = true;
+        } else if directive.eq_ignore_ascii_case("no-cache") {
+            directives.no_cache = true;
+        } else if let Some(seconds) = directive
+            .split('=')
+            .nth(1)
+            .and_then(|seconds| seconds.trim().parse::<u64>().ok())
+        {
+            if directive.to_ascii_lowercase().starts_with("max-age") {
+                directives.max_age = Some(Duration::from_secs(seconds));
+            }
+        }
+    }
+
+    directives
+}
+
+/// Caches `analyze_security_headers` results per URL across calls, so a
+/// bulk assessment re-scanning the same targets sends conditional
+/// requests (`If-None-Match` / `If-Modified-Since`) instead of
+/// re-downloading and re-parsing every response — the Actix
+/// `headers_check` handler holds one `HeaderScanner` shared across
+/// requests rather than constructing one per call.
+pub struct HeaderScanner {
+    client: reqwest::Client,
+    cache: Mutex<HashMap<String, CachedHeaders>>,
+}
+
+impl Default for HeaderScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeaderScanner {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .expect("default HTTP client configuration is always valid"),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Same as [`analyze_security_headers`], but reuses a cached result
+    /// while it's within its `Cache-Control: max-age` freshness lifetime,
+    /// or revalidates it with a conditional request and reuses it on a
+    /// `304 Not Modified` instead of re-parsing a full response.
+    pub async fn check(&self, url: &str) -> Result<crate::SecurityHeaders> {
+        if let Some(fresh) = self.fresh_cached(url) {
+            return Ok(fresh);
+        }
+
+        let (etag, last_modified) = self.validators(url);
+        let mut request = self.client.get(url);
+        if let Some(etag) = &etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request.send().await.context("Failed to send HTTP request")?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(cached) = self.cached(url) {
+                return Ok(cached.headers);
+            }
+        }
+
+        let parsed = security_headers_from(response.headers());
+        self.store(url, &response, parsed.clone());
+        Ok(parsed)
+    }
+
+    fn fresh_cached(&self, url: &str) -> Option<crate::SecurityHeaders> {
+        let cache = self.cache.lock().unwrap();
+        let cached = cache.get(url)?;
+        (cached.cacheable && cached.is_fresh()).then(|| cached.headers.clone())
+    }
+
+    fn cached(&self, url: &str) -> Option<CachedHeaders> {
+        self.cache.lock().unwrap().get(url).cloned()
+    }
+
+    fn validators(&self, url: &str) -> (Option<String>, Option<String>) {
+        match self.cache.lock().unwrap().get(url) {
+            Some(cached) => (cached.etag.clone(), cached.last_modified.clone()),
+            None => (None, None),
+        }
+    }
+
+    fn store(&self, url: &str, response: &reqwest::Response, headers: crate::SecurityHeaders) {
+        let cache_control = response
+            .headers()
+            .get(reqwest::header::CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .map(parse_cache_control);
+
+        if matches!(&cache_control, Some(directives) if directives.no_store) {
+            self.cache.lock().unwrap().remove(url);
+            return;
+        }
+
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        let last_modified = response
+            .headers()
+            .get(reqwest::header::LAST_MODIFIED)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        let max_age = cache_control
+            .as_ref()
+            .filter(|directives| !directives.no_cache)
+            .and_then(|directives| directives.max_age);
+        let cacheable = etag.is_some() || last_modified.is_some() || max_age.is_some();
+
+        self.cache.lock().unwrap().insert(
+            url.to_string(),
+            CachedHeaders {
+                headers,
+                etag,
+                last_modified,
+                fetched_at: Instant::now(),
+                max_age,
+                cacheable,
+            },
+        );
+    }
+}
+
+/// Caps how many redirect hops [`analyze_redirect_chain`] will follow
+/// before giving up, so a redirect loop can't hang a scan.
+const MAX_REDIRECT_HOPS: usize = 10;
+
+/// One hop of a redirect chain: the URL that was requested, the status
+/// it returned, its scheme, and the raw `Strict-Transport-Security`
+/// header value it sent (`None` if absent).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedirectHop {
+    pub url: String,
+    pub status: u16,
+    pub scheme: String,
+    pub hsts: Option<String>,
+}
+
+/// Walks `url`'s redirect chain by hand — reqwest's automatic redirect
+/// following is disabled — so intermediate hops can be inspected instead
+/// of only the final response. `analyze_security_headers` alone would
+/// miss a downgrade or a missing HSTS header that only shows up mid-chain.
+pub async fn analyze_redirect_chain(url: &str) -> Result<Vec<RedirectHop>> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let mut hops = Vec::new();
+    let mut current_url = url.to_string();
+
+    for _ in 0..MAX_REDIRECT_HOPS {
+        let response = client
+            .get(&current_url)
+            .send()
+            .await
+            .context("Failed to send HTTP request")?;
+
+        let status = response.status();
+        let scheme = url::Url::parse(&current_url)
+            .map(|parsed| parsed.scheme().to_string())
+            .unwrap_or_default();
+        let hsts = response
+            .headers()
+            .get("strict-transport-security")
+            .and_then(|h| h.to_str().ok())
+            .map(|s| s.to_string());
+        let next_location = status
+            .is_redirection()
+            .then(|| response.headers().get(reqwest::header::LOCATION).cloned())
+            .flatten()
+            .and_then(|h| h.to_str().ok().map(|s| s.to_string()));
+
+        hops.push(RedirectHop {
+            url: current_url.clone(),
+            status: status.as_u16(),
+            scheme,
+            hsts,
+        });
+
+        match next_location {
+            Some(location) => current_url = resolve_redirect_location(&current_url, &location)?,
+            None => break,
+        }
+    }
+
+    Ok(hops)
+}
+
+fn resolve_redirect_location(base: &str, location: &str) -> Result<String> {
+    let base = url::Url::parse(base).context("Invalid base URL in redirect chain")?;
+    let resolved = base
+        .join(location)
+        .context("Invalid Location header in redirect chain")?;
+    Ok(resolved.to_string())
+}
+
+/// Derives `HeaderVulnerability` findings from a whole redirect chain: a
+/// plain-HTTP entry point that never upgrades, a chain that dips back
+/// through HTTP after already having upgraded, and (at the last HTTPS hop
+/// reached) HSTS directive-level gaps.
+fn redirect_chain_vulnerabilities(chain: &[RedirectHop]) -> Vec<HeaderVulnerability> {
     let mut vulnerabilities = Vec::new();
-    let headers = analyze_security_headers(url).await?;
-    
-    // Check for missing HSTS
-    if headers.strict_transport_security.is_none() {
+
+    let starts_on_http = chain.first().map(|hop| hop.scheme == "http").unwrap_or(false);
+    let ever_reaches_https = chain.iter().any(|hop| hop.scheme == "https");
+
+    if starts_on_http && !ever_reaches_https {
+        vulnerabilities.push(HeaderVulnerability {
+            header: "Location".to_string(),
+            severity: VulnerabilitySeverity::Critical,
+            description: "Plain-HTTP entry point never upgrades to HTTPS".to_string(),
+            recommendation: "Redirect all HTTP traffic to an HTTPS equivalent".to_string(),
+        });
+    } else if chain
+        .iter()
+        .skip_while(|hop| hop.scheme != "https")
+        .any(|hop| hop.scheme == "http")
+    {
         vulnerabilities.push(HeaderVulnerability {
+            header: "Location".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "Redirect chain bounces back through plain HTTP after upgrading to HTTPS".to_string(),
+            recommendation: "Keep every subsequent hop on HTTPS once the chain has upgraded".to_string(),
+        });
+    }
+
+    if let Some(last_https_hop) = chain.iter().rev().find(|hop| hop.scheme == "https") {
+        vulnerabilities.extend(hsts_vulnerabilities(last_https_hop));
+    }
+
+    vulnerabilities
+}
+
+/// The HSTS preload list's minimum `max-age`, in seconds (one year).
+const HSTS_PRELOAD_MIN_MAX_AGE: u64 = 31536000;
+
+fn hsts_vulnerabilities(hop: &RedirectHop) -> Vec<HeaderVulnerability> {
+    let Some(hsts_value) = &hop.hsts else {
+        return vec![HeaderVulnerability {
             header: "Strict-Transport-Security".to_string(),
             severity: VulnerabilitySeverity::High,
-            description: "Missing HSTS header".to_string(),
-            recommendation: "Add Strict-Transport-Security header".to_string(),
+            description: format!("{} has no Strict-Transport-Security header", hop.url),
+            recommendation: "Add a Strict-Transport-Security header".to_string(),
+        }];
+    };
+
+    let directives: Vec<String> = hsts_value
+        .split(';')
+        .map(|directive| directive.trim().to_ascii_lowercase())
+        .collect();
+    let max_age = directives
+        .iter()
+        .find_map(|directive| directive.strip_prefix("max-age=").and_then(|v| v.parse::<u64>().ok()));
+    let has_include_subdomains = directives.iter().any(|d| d == "includesubdomains");
+    let has_preload = directives.iter().any(|d| d == "preload");
+
+    let mut vulnerabilities = Vec::new();
+
+    if max_age.map(|age| age < HSTS_PRELOAD_MIN_MAX_AGE).unwrap_or(true) {
+        vulnerabilities.push(HeaderVulnerability {
+            header: "Strict-Transport-Security".to_string(),
+            severity: VulnerabilitySeverity::Medium,
+            description: format!(
+                "HSTS max-age is below the one-year preload-list minimum ({}s)",
+                HSTS_PRELOAD_MIN_MAX_AGE
+            ),
+            recommendation: format!("Set max-age to at least {} seconds", HSTS_PRELOAD_MIN_MAX_AGE),
         });
     }
-    
-    // Check for missing CSP
-    if headers.content_security_policy.is_none() {
+
+    if !has_include_subdomains || !has_preload {
         vulnerabilities.push(HeaderVulnerability {
+            header: "Strict-Transport-Security".to_string(),
+            severity: VulnerabilitySeverity::Low,
+            description: "HSTS header is missing includeSubDomains and/or preload, making the site ineligible for the HSTS preload list".to_string(),
+            recommendation: "Add includeSubDomains and preload to the Strict-Transport-Security header".to_string(),
+        });
+    }
+
+    vulnerabilities
+}
+
+pub async fn check_security_header_vulnerabilities(url: &str) -> Result<Vec<HeaderVulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let headers = analyze_security_headers(url).await?;
+
+    // HSTS is assessed through the redirect chain below (on the last
+    // HTTPS hop reached, alongside a check for a downgrade back to plain
+    // HTTP), rather than as a single presence check on the endpoint.
+    let chain = analyze_redirect_chain(url).await?;
+    vulnerabilities.extend(redirect_chain_vulnerabilities(&chain));
+
+    // Check CSP: a directive-level finding per risky directive when
+    // present, or a single missing-header finding when absent.
+    match &headers.content_security_policy {
+        Some(csp) => vulnerabilities.extend(csp_vulnerabilities(&parse_csp(csp))),
+        None => vulnerabilities.push(HeaderVulnerability {
             header: "Content-Security-Policy".to_string(),
             severity: VulnerabilitySeverity::Medium,
             description: "Missing CSP header".to_string(),
             recommendation: "Add Content-Security-Policy header".to_string(),
-        });
+        }),
     }
-    
+
+    // Check Permissions-Policy the same way: flag each powerful feature
+    // left unlocked when present, or the header's absence entirely.
+    match &headers.permissions_policy {
+        Some(policy) => {
+            vulnerabilities.extend(permissions_policy_vulnerabilities(&parse_permissions_policy(policy)))
+        }
+        None => vulnerabilities.push(HeaderVulnerability {
+            header: "Permissions-Policy".to_string(),
+            severity: VulnerabilitySeverity::Medium,
+            description: "Missing Permissions-Policy header".to_string(),
+            recommendation: "Add a Permissions-Policy header locking down powerful features".to_string(),
+        }),
+    }
+
     // Check for missing X-Frame-Options
     if headers.x_frame_options.is_none() {
         vulnerabilities.push(HeaderVulnerability {
@@ -81,7 +441,7 @@ pub async fn check_security_header_vulnerabilities(url: &str) -> Result<Vec<Head
             recommendation: "Add X-Frame-Options header".to_string(),
         });
     }
-    
+
     // Check for missing X-Content-Type-Options
     if headers.x_content_type_options.is_none() {
         vulnerabilities.push(HeaderVulnerability {
@@ -91,10 +451,164 @@ pub async fn check_security_header_vulnerabilities(url: &str) -> Result<Vec<Head
             recommendation: "Add X-Content-Type-Options: nosniff".to_string(),
         });
     }
-    
+
     Ok(vulnerabilities)
 }
 
+/// Powerful features worth flagging when left unlocked, mirroring the
+/// Permissions Policy features most often abused for fingerprinting or
+/// unwanted hardware/data access.
+const DANGEROUS_PERMISSIONS_FEATURES: &[&str] = &[
+    "camera",
+    "microphone",
+    "geolocation",
+    "payment",
+    "usb",
+    "accelerometer",
+    "gyroscope",
+    "magnetometer",
+    "midi",
+    "display-capture",
+];
+
+/// A parsed `Permissions-Policy` header: each feature mapped to its
+/// allowlist (an empty allowlist means the feature is disabled
+/// everywhere, including the top-level document).
+#[derive(Debug, Clone)]
+pub struct PermissionsPolicy {
+    pub features: HashMap<String, Vec<String>>,
+}
+
+/// Splits a `Permissions-Policy` header on commas into
+/// `feature=(allowlist)` pairs.
+pub fn parse_permissions_policy(value: &str) -> PermissionsPolicy {
+    let mut features = HashMap::new();
+
+    for directive in value.split(',') {
+        let directive = directive.trim();
+        let Some((feature, rest)) = directive.split_once('=') else {
+            continue;
+        };
+
+        let allowlist_str = rest.trim().trim_start_matches('(').trim_end_matches(')');
+        let allowlist = allowlist_str
+            .split_whitespace()
+            .map(|origin| origin.trim_matches('"').to_string())
+            .collect();
+
+        features.insert(feature.trim().to_string(), allowlist);
+    }
+
+    PermissionsPolicy { features }
+}
+
+fn permissions_policy_vulnerabilities(policy: &PermissionsPolicy) -> Vec<HeaderVulnerability> {
+    DANGEROUS_PERMISSIONS_FEATURES
+        .iter()
+        .filter_map(|feature| {
+            let allowlist = policy.features.get(*feature);
+            let locked_down = allowlist.map(|list| list.is_empty()).unwrap_or(false);
+            if locked_down {
+                return None;
+            }
+
+            let allowlist_description = match allowlist {
+                Some(list) => list.join(" "),
+                None => "not declared, defaults to allowed".to_string(),
+            };
+
+            Some(HeaderVulnerability {
+                header: "Permissions-Policy".to_string(),
+                severity: VulnerabilitySeverity::Medium,
+                description: format!(
+                    "Powerful feature '{}' is not locked to an empty allowlist ({})",
+                    feature, allowlist_description
+                ),
+                recommendation: format!("Set {}=() unless the feature is actually needed", feature),
+            })
+        })
+        .collect()
+}
+
+/// A parsed `Content-Security-Policy` header: each directive mapped to
+/// its space-separated source list.
+#[derive(Debug, Clone)]
+pub struct ContentSecurityPolicy {
+    pub directives: HashMap<String, Vec<String>>,
+}
+
+/// Tokenizes a CSP header into directives and their source lists.
+pub fn parse_csp(value: &str) -> ContentSecurityPolicy {
+    let mut directives = HashMap::new();
+
+    for directive in value.split(';') {
+        let mut tokens = directive.split_whitespace();
+        let Some(name) = tokens.next() else {
+            continue;
+        };
+        directives.insert(name.to_ascii_lowercase(), tokens.map(|s| s.to_string()).collect());
+    }
+
+    ContentSecurityPolicy { directives }
+}
+
+fn csp_vulnerabilities(csp: &ContentSecurityPolicy) -> Vec<HeaderVulnerability> {
+    let mut vulnerabilities = Vec::new();
+
+    for (directive, sources) in &csp.directives {
+        if sources.iter().any(|s| s == "'unsafe-inline'") {
+            vulnerabilities.push(HeaderVulnerability {
+                header: "Content-Security-Policy".to_string(),
+                severity: VulnerabilitySeverity::High,
+                description: format!("{} allows 'unsafe-inline'", directive),
+                recommendation: format!(
+                    "Remove 'unsafe-inline' from {} and use nonces or hashes instead",
+                    directive
+                ),
+            });
+        }
+        if sources.iter().any(|s| s == "'unsafe-eval'") {
+            vulnerabilities.push(HeaderVulnerability {
+                header: "Content-Security-Policy".to_string(),
+                severity: VulnerabilitySeverity::High,
+                description: format!("{} allows 'unsafe-eval'", directive),
+                recommendation: format!("Remove 'unsafe-eval' from {}", directive),
+            });
+        }
+        if sources.iter().any(|s| s == "*") {
+            vulnerabilities.push(HeaderVulnerability {
+                header: "Content-Security-Policy".to_string(),
+                severity: VulnerabilitySeverity::Medium,
+                description: format!("{} allows any origin via a wildcard source", directive),
+                recommendation: format!(
+                    "Scope {} to a specific allowlist of origins instead of *",
+                    directive
+                ),
+            });
+        }
+    }
+
+    if !csp.directives.contains_key("default-src") {
+        vulnerabilities.push(HeaderVulnerability {
+            header: "Content-Security-Policy".to_string(),
+            severity: VulnerabilitySeverity::Medium,
+            description: "CSP has no default-src directive".to_string(),
+            recommendation: "Add a default-src directive as a fallback for unlisted resource types"
+                .to_string(),
+        });
+    }
+    if !csp.directives.contains_key("frame-ancestors") {
+        vulnerabilities.push(HeaderVulnerability {
+            header: "Content-Security-Policy".to_string(),
+            severity: VulnerabilitySeverity::Low,
+            description: "CSP has no frame-ancestors directive".to_string(),
+            recommendation: "Add frame-ancestors to control which sites may frame this page".to_string(),
+        });
+    }
+
+    vulnerabilities
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HeaderVulnerability {
     pub header: String,
@@ -111,19 +625,54 @@ pub enum VulnerabilitySeverity {
     Critical,
 }
 
+/// A weighted score in `[0, 1]`. Presence-only headers (HSTS,
+/// X-Frame-Options, X-Content-Type-Options, X-XSS-Protection,
+/// Referrer-Policy) are worth one point each; Permissions-Policy and CSP
+/// are worth up to two points each — one for being present, one scaled by
+/// how well-locked-down the directives actually are — since a present but
+/// wide-open policy shouldn't score the same as a tight one.
 pub fn calculate_security_header_score(headers: &crate::SecurityHeaders) -> f64 {
     let mut score = 0.0;
-    let total_checks = 7.0;
-    
-    if headers.strict_transport_security.is_some() { score += 1.0; }
-    if headers.content_security_policy.is_some() { score += 1.0; }
-    if headers.x_frame_options.is_some() { score += 1.0; }
-    if headers.x_content_type_options.is_some() { score += 1.0; }
-    if headers.x_xss_protection.is_some() { score += 1.0; }
-    if headers.referrer_policy.is_some() { score += 1.0; }
-    if headers.permissions_policy.is_some() { score += 1.0; }
-    
-    score / total_checks
+    let mut max_score = 0.0;
+
+    for present in [
+        headers.strict_transport_security.is_some(),
+        headers.x_frame_options.is_some(),
+        headers.x_content_type_options.is_some(),
+        headers.x_xss_protection.is_some(),
+        headers.referrer_policy.is_some(),
+    ] {
+        max_score += 1.0;
+        if present {
+            score += 1.0;
+        }
+    }
+
+    max_score += 2.0;
+    if let Some(value) = &headers.permissions_policy {
+        score += 1.0;
+        let policy = parse_permissions_policy(value);
+        let locked_down = DANGEROUS_PERMISSIONS_FEATURES
+            .iter()
+            .filter(|feature| {
+                policy
+                    .features
+                    .get(**feature)
+                    .map(|allowlist| allowlist.is_empty())
+                    .unwrap_or(false)
+            })
+            .count();
+        score += locked_down as f64 / DANGEROUS_PERMISSIONS_FEATURES.len() as f64;
+    }
+
+    max_score += 2.0;
+    if let Some(value) = &headers.content_security_policy {
+        score += 1.0;
+        let risky_directive_count = csp_vulnerabilities(&parse_csp(value)).len();
+        score += (1.0 - risky_directive_count as f64 * 0.2).max(0.0);
+    }
+
+    score / max_score
 }
 
 #[cfg(test)]
@@ -138,18 +687,147 @@ mod tests {
     }
 
     #[test]
-    fn test_security_header_score() {
+    fn test_parse_cache_control_max_age() {
+        let directives = parse_cache_control("public, max-age=3600");
+        assert!(!directives.no_store);
+        assert!(!directives.no_cache);
+        assert_eq!(directives.max_age, Some(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_parse_cache_control_no_store_and_no_cache() {
+        let directives = parse_cache_control("no-store");
+        assert!(directives.no_store);
+
+        let directives = parse_cache_control("no-cache, max-age=60");
+        assert!(directives.no_cache);
+        assert_eq!(directives.max_age, Some(Duration::from_secs(60)));
+    }
+
+    fn hop(url: &str, scheme: &str, hsts: Option<&str>) -> RedirectHop {
+        RedirectHop {
+            url: url.to_string(),
+            status: 200,
+            scheme: scheme.to_string(),
+            hsts: hsts.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_http_entry_point_never_upgrading_is_flagged() {
+        let chain = vec![hop("http://example.com", "http", None)];
+        let vulnerabilities = redirect_chain_vulnerabilities(&chain);
+        assert!(vulnerabilities
+            .iter()
+            .any(|v| v.description.contains("never upgrades")));
+    }
+
+    #[test]
+    fn test_chain_bouncing_through_http_midway_is_flagged() {
+        let chain = vec![
+            hop("https://example.com", "https", Some("max-age=31536000")),
+            hop("http://example.com/mid", "http", None),
+            hop(
+                "https://example.com/final",
+                "https",
+                Some("max-age=31536000; includeSubDomains; preload"),
+            ),
+        ];
+        let vulnerabilities = redirect_chain_vulnerabilities(&chain);
+        assert!(vulnerabilities
+            .iter()
+            .any(|v| v.description.contains("bounces back through plain HTTP")));
+    }
+
+    #[test]
+    fn test_hsts_short_max_age_and_missing_preload_directives_are_flagged() {
+        let chain = vec![hop("https://example.com", "https", Some("max-age=100"))];
+        let vulnerabilities = redirect_chain_vulnerabilities(&chain);
+        assert!(vulnerabilities
+            .iter()
+            .any(|v| v.description.contains("preload-list minimum")));
+        assert!(vulnerabilities
+            .iter()
+            .any(|v| v.description.contains("ineligible for the HSTS preload list")));
+    }
+
+    #[test]
+    fn test_well_formed_hsts_is_not_flagged() {
+        let chain = vec![hop(
+            "https://example.com",
+            "https",
+            Some("max-age=31536000; includeSubDomains; preload"),
+        )];
+        assert!(redirect_chain_vulnerabilities(&chain).is_empty());
+    }
+
+    #[test]
+    fn test_security_header_score_perfect() {
         let headers = crate::SecurityHeaders {
             strict_transport_security: Some("max-age=31536000".to_string()),
-            content_security_policy: Some("default-src 'self'".to_string()),
+            content_security_policy: Some("default-src 'self'; frame-ancestors 'self'".to_string()),
             x_frame_options: Some("DENY".to_string()),
             x_content_type_options: Some("nosniff".to_string()),
             x_xss_protection: Some("1; mode=block".to_string()),
             referrer_policy: Some("strict-origin-when-cross-origin".to_string()),
-            permissions_policy: Some("geolocation=()".to_string()),
+            permissions_policy: Some(
+                "camera=(), microphone=(), geolocation=(), payment=(), usb=(), \
+                 accelerometer=(), gyroscope=(), magnetometer=(), midi=(), display-capture=()"
+                    .to_string(),
+            ),
         };
-        
+
         let score = calculate_security_header_score(&headers);
         assert_eq!(score, 1.0);
     }
+
+    #[test]
+    fn test_security_header_score_penalizes_wide_open_policies() {
+        let locked_down = crate::SecurityHeaders {
+            strict_transport_security: Some("max-age=31536000".to_string()),
+            content_security_policy: Some("default-src 'self'; frame-ancestors 'self'".to_string()),
+            x_frame_options: Some("DENY".to_string()),
+            x_content_type_options: Some("nosniff".to_string()),
+            x_xss_protection: Some("1; mode=block".to_string()),
+            referrer_policy: Some("strict-origin-when-cross-origin".to_string()),
+            permissions_policy: Some("geolocation=()".to_string()),
+        };
+
+        let wide_open = crate::SecurityHeaders {
+            content_security_policy: Some("default-src *; script-src 'unsafe-inline' 'unsafe-eval'".to_string()),
+            permissions_policy: Some("camera=*, microphone=*".to_string()),
+            ..locked_down.clone()
+        };
+
+        assert!(calculate_security_header_score(&wide_open) < calculate_security_header_score(&locked_down));
+    }
+
+    #[test]
+    fn test_parse_permissions_policy_allowlists() {
+        let policy = parse_permissions_policy("geolocation=(), camera=(\"self\"), microphone=*");
+        assert_eq!(policy.features.get("geolocation"), Some(&vec![]));
+        assert_eq!(policy.features.get("camera"), Some(&vec!["self".to_string()]));
+        assert_eq!(policy.features.get("microphone"), Some(&vec!["*".to_string()]));
+    }
+
+    #[test]
+    fn test_permissions_policy_flags_unlocked_dangerous_features() {
+        let policy = parse_permissions_policy("geolocation=(), camera=*");
+        let vulnerabilities = permissions_policy_vulnerabilities(&policy);
+        assert!(!vulnerabilities.iter().any(|v| v.description.contains("geolocation")));
+        assert!(vulnerabilities.iter().any(|v| v.description.contains("camera")));
+        // Dangerous features not declared at all default to allowed too.
+        assert!(vulnerabilities.iter().any(|v| v.description.contains("microphone")));
+    }
+
+    #[test]
+    fn test_csp_flags_unsafe_directives_and_wildcards() {
+        let csp = parse_csp("default-src 'self'; script-src 'unsafe-inline' 'unsafe-eval' *");
+        let vulnerabilities = csp_vulnerabilities(&csp);
+        assert!(vulnerabilities.iter().any(|v| v.description.contains("unsafe-inline")));
+        assert!(vulnerabilities.iter().any(|v| v.description.contains("unsafe-eval")));
+        assert!(vulnerabilities.iter().any(|v| v.description.contains("wildcard")));
+        assert!(vulnerabilities.iter().any(|v| v.description.contains("frame-ancestors")));
+        assert!(!vulnerabilities.iter().any(|v| v.description.contains("default-src")));
+    }
 }
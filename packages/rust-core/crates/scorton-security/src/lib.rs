@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
+use hickory_resolver::TokioAsyncResolver;
 use tokio::net::TcpStream;
 use tokio::time::timeout;
 use url::Url;
@@ -12,6 +13,17 @@ pub mod compliance;
 pub mod ssl;
 pub mod dns;
 pub mod headers;
+pub mod revocation;
+pub mod metrics;
+pub mod store;
+pub mod policy;
+pub mod provenance;
+pub mod dnssec;
+pub mod encrypted_dns;
+#[cfg(test)]
+mod test_harness;
+#[cfg(test)]
+mod integration_tests;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanResult {
@@ -58,6 +70,25 @@ pub struct SSLCertificate {
     pub key_size: u32,
     pub serial_number: String,
     pub san: Vec<String>,
+    pub key_type: KeyType,
+    pub curve: Option<String>,
+    pub chain: Vec<ChainCertificate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainCertificate {
+    pub subject: String,
+    pub issuer: String,
+    pub signature_algorithm: String,
+    pub key_size: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum KeyType {
+    Rsa,
+    Ecdsa,
+    Ed25519,
+    Unknown,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,67 +113,99 @@ pub struct SecurityHeaders {
 pub struct SecurityScanner {
     timeout: Duration,
     max_concurrent: usize,
+    /// Shared across every concurrent scan task (`TokioAsyncResolver` is
+    /// itself a cheap `Clone + Send + Sync` handle), so `port_scan` and
+    /// `dns_enum` resolve names through it rather than the OS stub
+    /// resolver via `ToSocketAddrs`. `ssl_scan` and the encrypted-DNS
+    /// probes connect by hostname and resolve through the OS resolver.
+    resolver: TokioAsyncResolver,
 }
 
 impl Default for SecurityScanner {
     fn default() -> Self {
-        Self {
-            timeout: Duration::from_secs(30),
-            max_concurrent: 100,
-        }
+        Self::new(Duration::from_secs(30), 100)
     }
 }
 
 impl SecurityScanner {
     pub fn new(timeout: Duration, max_concurrent: usize) -> Self {
-        Self {
+        Self::new_with_resolver_config(timeout, max_concurrent, dns::ResolverConfig::default())
+            .expect("default resolver configuration is always constructible")
+    }
+
+    /// Same as [`Self::new`], but resolves names through a resolver built
+    /// from `resolver_config` — upstream nameservers, transport
+    /// (UDP/TCP/DoT/DoH), per-query timeout/retry count, and EDNS —
+    /// instead of the default bootstrap resolver. Use this to target a
+    /// specific recursive resolver, honor a split-horizon setup, or avoid
+    /// the OS stub resolver entirely.
+    pub fn new_with_resolver_config(
+        timeout: Duration,
+        max_concurrent: usize,
+        resolver_config: dns::ResolverConfig,
+    ) -> Result<Self> {
+        Ok(Self {
             timeout,
             max_concurrent,
-        }
+            resolver: dns::build_resolver(&resolver_config)?,
+        })
     }
 
     pub async fn port_scan(&self, target: &str, ports: &[u16]) -> Result<Vec<PortScanResult>> {
-        let start_time = std::time::Instant::now();
+        let (sender, _receiver) = tokio::sync::broadcast::channel(16);
+        self.port_scan_streaming(target, ports, sender).await
+    }
+
+    /// Same as [`Self::port_scan`], but pushes each `PortScanResult` over
+    /// `events` as soon as that port's task finishes, rather than waiting
+    /// for the whole join set to drain — so a WebSocket handler can relay
+    /// real-time progress for a large port list.
+    pub async fn port_scan_streaming(
+        &self,
+        target: &str,
+        ports: &[u16],
+        events: tokio::sync::broadcast::Sender<PortScanResult>,
+    ) -> Result<Vec<PortScanResult>> {
         let mut results = Vec::new();
-        
-        // Parse target to IP address
+
+        // Parse target to IP address, resolving through the shared
+        // resolver (not the OS stub resolver) if it isn't one already.
         let ip: IpAddr = if let Ok(parsed_ip) = target.parse() {
             parsed_ip
         } else {
-            // Try DNS resolution if not an IP
-            let target = target.to_string();
-            let result = tokio::task::spawn_blocking(move || {
-                std::net::ToSocketAddrs::to_socket_addrs(&format!("{}:80", target))
-                    .map(|mut addrs| addrs.next().map(|addr| addr.ip()))
-                    .unwrap_or(None)
-            }).await.map_err(|_| anyhow::anyhow!("DNS resolution task failed"))?;
-            
-            match result {
-                Some(ip) => ip,
-                None => return Err(anyhow::anyhow!("No IP address found for target")),
-            }
+            let lookup = self
+                .resolver
+                .lookup_ip(target)
+                .await
+                .context("DNS resolution failed")?;
+            lookup
+                .iter()
+                .next()
+                .context("No IP address found for target")?
         };
 
         // Create semaphore for concurrency control
         let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.max_concurrent));
-        
-        // Scan ports concurrently
-        let mut tasks = Vec::new();
+
+        // Scan ports concurrently, collecting in completion order (not
+        // spawn order) so streaming reflects real-time progress instead
+        // of stalling on whichever port happened to be queued first.
+        let mut tasks = tokio::task::JoinSet::new();
         let ports = ports.to_vec(); // Convert to owned Vec
         for port in ports {
             let semaphore = semaphore.clone();
             let ip = ip;
             let timeout = self.timeout;
-            
-            tasks.push(tokio::spawn(async move {
+
+            tasks.spawn(async move {
                 let _permit = semaphore.acquire().await.unwrap();
                 Self::scan_port(ip, port, timeout).await
-            }));
+            });
         }
 
-        // Collect results
-        for task in tasks {
-            if let Ok(result) = task.await {
+        while let Some(task) = tasks.join_next().await {
+            if let Ok(result) = task {
+                let _ = events.send(result.clone());
                 results.push(result);
             }
         }
@@ -204,12 +267,67 @@ impl SecurityScanner {
     }
 
     pub async fn dns_enum(&self, domain: &str) -> Result<Vec<DNSRecord>> {
-        dns::enumerate_dns_records(domain).await
+        Ok(dns::enumerate_with_resolver(&self.resolver, domain, &dns::RecordType::ALL).await)
+    }
+
+    /// Same as [`Self::dns_enum`], but pushes each `DNSRecord` over
+    /// `events` as soon as its record type's lookup finishes.
+    pub async fn dns_enum_streaming(
+        &self,
+        domain: &str,
+        events: tokio::sync::broadcast::Sender<DNSRecord>,
+    ) -> Result<Vec<DNSRecord>> {
+        Ok(dns::enumerate_with_resolver_streaming(&self.resolver, domain, &dns::RecordType::ALL, events).await)
+    }
+
+    /// Same as [`Self::dns_enum`], plus a DNSSEC validation pass so
+    /// `NIS2Assessment`-style compliance reporting can flag zones lacking
+    /// DNSSEC or serving a broken chain of trust.
+    pub async fn dns_enum_with_dnssec(
+        &self,
+        domain: &str,
+    ) -> Result<(Vec<DNSRecord>, dnssec::DnssecReport)> {
+        let records = self.dns_enum(domain).await?;
+        let report = dnssec::DnssecValidator::new()
+            .validate(domain, &dns::ResolverConfig::default())
+            .await?;
+        Ok((records, report))
+    }
+
+    /// Same as [`Self::dns_enum`], plus the CAA/TLSA/DNSSEC posture checks
+    /// a security scan cares about — not just the raw record dump, but
+    /// whether the zone restricts certificate issuance, publishes DANE
+    /// pins for its HTTPS host, and is signed.
+    pub async fn dns_enum_with_security_posture(
+        &self,
+        domain: &str,
+    ) -> Result<(Vec<DNSRecord>, dns::DnsSecurityPosture)> {
+        let records = self.dns_enum(domain).await?;
+        let posture = dns::assess_dns_security_posture(&self.resolver, domain, &records).await;
+        Ok((records, posture))
     }
 
     pub async fn check_headers(&self, url: &str) -> Result<SecurityHeaders> {
         headers::analyze_security_headers(url).await
     }
+
+    /// Probes `target` for encrypted DNS transport support: a real TLS
+    /// handshake against DoT (853) and DoH's HTTPS layer (443), each
+    /// attempted independently so one being absent doesn't fail the other.
+    pub async fn probe_encrypted_dns(&self, target: &str) -> encrypted_dns::EncryptedDnsSupport {
+        encrypted_dns::EncryptedDnsSupport {
+            dot: encrypted_dns::probe_dot(target).await.ok(),
+            doh: encrypted_dns::probe_doh(target).await.ok(),
+            dnscrypt: None,
+        }
+    }
+
+    /// Probes a DNSCrypt resolver described by an `sdns://` stamp,
+    /// separately from [`Self::probe_encrypted_dns`] since DNSCrypt is
+    /// identified by its stamp rather than a bare hostname/IP.
+    pub async fn probe_dnscrypt_stamp(&self, stamp: &str) -> Result<encrypted_dns::DnsCryptProbeResult> {
+        encrypted_dns::probe_dnscrypt(stamp).await
+    }
 }
 
 #[cfg(test)]
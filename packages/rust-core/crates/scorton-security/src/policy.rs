@@ -0,0 +1,287 @@
+use anyhow::{bail, Result};
+use std::time::Duration;
+
+use crate::compliance::{BCPStatus, ComplianceStatus, IncidentMetrics, SupplyChainScore, ThirdPartyRisk};
+
+/// Identifies an individual regulatory control a [`CompliancePolicy`]
+/// evaluates, so new controls (and eventually other frameworks like ISO
+/// 27001 or SOC2) can be registered without editing `ComplianceAssessor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PolicyId {
+    IncidentResponseTime,
+    IncidentReportingTime,
+    Resilience,
+    ThirdPartyRisk,
+    BusinessContinuity,
+    SupplyChain,
+}
+
+/// The bundle of assessment data a policy needs to render a verdict.
+/// Fields are optional because not every assessment run populates every
+/// one of them (a DORA-only run has no `business_continuity`, etc).
+#[derive(Debug, Clone, Default)]
+pub struct AssessmentContext {
+    pub ict_risk_score: Option<f64>,
+    pub incident_response_time: Option<Duration>,
+    pub third_party_risks: Option<Vec<ThirdPartyRisk>>,
+    pub resilience_score: Option<f64>,
+    pub incident_handling: Option<IncidentMetrics>,
+    pub business_continuity: Option<BCPStatus>,
+    pub supply_chain_security: Option<SupplyChainScore>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyOutcome {
+    Pass,
+    Fail { reason: String },
+    /// The context didn't carry the data this policy needs; excluded
+    /// from the aggregate rather than counted as a failure.
+    NotApplicable,
+}
+
+/// A single regulatory control, evaluated independently so new controls
+/// can be registered without touching the assessor's aggregation logic.
+pub trait CompliancePolicy: Send + Sync {
+    fn id(&self) -> PolicyId;
+    fn evaluate(&self, ctx: &AssessmentContext) -> PolicyOutcome;
+}
+
+pub struct IncidentResponseTimePolicy {
+    pub max_incident_response_time: Duration,
+}
+
+impl CompliancePolicy for IncidentResponseTimePolicy {
+    fn id(&self) -> PolicyId {
+        PolicyId::IncidentResponseTime
+    }
+
+    fn evaluate(&self, ctx: &AssessmentContext) -> PolicyOutcome {
+        match ctx.incident_response_time {
+            Some(actual) if actual <= self.max_incident_response_time => PolicyOutcome::Pass,
+            Some(actual) => PolicyOutcome::Fail {
+                reason: format!(
+                    "incident response time {:?} exceeds the {:?} limit",
+                    actual, self.max_incident_response_time
+                ),
+            },
+            None => PolicyOutcome::NotApplicable,
+        }
+    }
+}
+
+pub struct IncidentReportingTimePolicy {
+    pub max_incident_reporting_time: Duration,
+}
+
+impl CompliancePolicy for IncidentReportingTimePolicy {
+    fn id(&self) -> PolicyId {
+        PolicyId::IncidentReportingTime
+    }
+
+    fn evaluate(&self, ctx: &AssessmentContext) -> PolicyOutcome {
+        match &ctx.incident_handling {
+            Some(metrics) if metrics.reporting_time <= self.max_incident_reporting_time => PolicyOutcome::Pass,
+            Some(metrics) => PolicyOutcome::Fail {
+                reason: format!(
+                    "incident reporting time {:?} exceeds the {:?} limit",
+                    metrics.reporting_time, self.max_incident_reporting_time
+                ),
+            },
+            None => PolicyOutcome::NotApplicable,
+        }
+    }
+}
+
+pub struct ResiliencePolicy {
+    pub min_resilience_score: f64,
+}
+
+impl CompliancePolicy for ResiliencePolicy {
+    fn id(&self) -> PolicyId {
+        PolicyId::Resilience
+    }
+
+    fn evaluate(&self, ctx: &AssessmentContext) -> PolicyOutcome {
+        match ctx.resilience_score {
+            Some(actual) if actual >= self.min_resilience_score => PolicyOutcome::Pass,
+            Some(actual) => PolicyOutcome::Fail {
+                reason: format!(
+                    "resilience score {} is below the {} minimum",
+                    actual, self.min_resilience_score
+                ),
+            },
+            None => PolicyOutcome::NotApplicable,
+        }
+    }
+}
+
+pub struct ThirdPartyRiskPolicy {
+    pub max_risk: crate::compliance::RiskLevel,
+}
+
+impl CompliancePolicy for ThirdPartyRiskPolicy {
+    fn id(&self) -> PolicyId {
+        PolicyId::ThirdPartyRisk
+    }
+
+    fn evaluate(&self, ctx: &AssessmentContext) -> PolicyOutcome {
+        use crate::compliance::RiskLevel;
+
+        let Some(risks) = &ctx.third_party_risks else {
+            return PolicyOutcome::NotApplicable;
+        };
+
+        let max_rank = risk_rank(&self.max_risk);
+        let offenders: Vec<&str> = risks
+            .iter()
+            .filter(|risk| risk_rank(&risk.risk_level) > max_rank)
+            .map(|risk| risk.vendor_name.as_str())
+            .collect();
+
+        if offenders.is_empty() {
+            PolicyOutcome::Pass
+        } else {
+            PolicyOutcome::Fail {
+                reason: format!("vendors exceeding risk tolerance: {}", offenders.join(", ")),
+            }
+        }
+    }
+}
+
+fn risk_rank(risk: &crate::compliance::RiskLevel) -> u8 {
+    use crate::compliance::RiskLevel;
+    match risk {
+        RiskLevel::Low => 0,
+        RiskLevel::Medium => 1,
+        RiskLevel::High => 2,
+        RiskLevel::Critical => 3,
+    }
+}
+
+pub struct BusinessContinuityPolicy {
+    pub min_bcp_score: f64,
+}
+
+impl CompliancePolicy for BusinessContinuityPolicy {
+    fn id(&self) -> PolicyId {
+        PolicyId::BusinessContinuity
+    }
+
+    fn evaluate(&self, ctx: &AssessmentContext) -> PolicyOutcome {
+        let Some(bcp) = &ctx.business_continuity else {
+            return PolicyOutcome::NotApplicable;
+        };
+
+        if bcp.plan_exists && bcp.last_tested.is_some() {
+            PolicyOutcome::Pass
+        } else {
+            PolicyOutcome::Fail {
+                reason: "no tested business continuity plan on file".to_string(),
+            }
+        }
+    }
+}
+
+pub struct SupplyChainPolicy {
+    pub min_supply_chain_score: f64,
+}
+
+impl CompliancePolicy for SupplyChainPolicy {
+    fn id(&self) -> PolicyId {
+        PolicyId::SupplyChain
+    }
+
+    fn evaluate(&self, ctx: &AssessmentContext) -> PolicyOutcome {
+        match &ctx.supply_chain_security {
+            Some(score) if score.overall_score >= self.min_supply_chain_score => PolicyOutcome::Pass,
+            Some(score) => PolicyOutcome::Fail {
+                reason: format!(
+                    "supply chain score {} is below the {} minimum",
+                    score.overall_score, self.min_supply_chain_score
+                ),
+            },
+            None => PolicyOutcome::NotApplicable,
+        }
+    }
+}
+
+/// Aggregates every applicable policy's outcome into a single
+/// `ComplianceStatus`: compliant only if every applicable policy passed,
+/// non-compliant only if every applicable policy failed, partially
+/// compliant otherwise.
+pub fn aggregate(outcomes: &[PolicyOutcome]) -> ComplianceStatus {
+    let applicable: Vec<&PolicyOutcome> = outcomes
+        .iter()
+        .filter(|outcome| !matches!(outcome, PolicyOutcome::NotApplicable))
+        .collect();
+
+    if applicable.is_empty() {
+        return ComplianceStatus::Unknown;
+    }
+
+    let passes = applicable
+        .iter()
+        .filter(|outcome| matches!(outcome, PolicyOutcome::Pass))
+        .count();
+
+    if passes == applicable.len() {
+        ComplianceStatus::Compliant
+    } else if passes == 0 {
+        ComplianceStatus::NonCompliant
+    } else {
+        ComplianceStatus::PartiallyCompliant
+    }
+}
+
+/// Validates that a policy bundle's parameters are internally consistent
+/// before the assessor starts using them, e.g. the business continuity
+/// RTO must not exceed the max incident response time (a BCP that's
+/// "allowed" to take longer to recover than incidents are allowed to
+/// take to respond to isn't coherent).
+pub fn validate_policy_parameters(
+    rto: Duration,
+    max_incident_response_time: Duration,
+) -> Result<()> {
+    if rto > max_incident_response_time {
+        bail!(
+            "recovery time objective {:?} exceeds max incident response time {:?}",
+            rto,
+            max_incident_response_time
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_all_pass_is_compliant() {
+        let outcomes = vec![PolicyOutcome::Pass, PolicyOutcome::Pass];
+        assert_eq!(aggregate(&outcomes), ComplianceStatus::Compliant);
+    }
+
+    #[test]
+    fn test_aggregate_mixed_is_partial() {
+        let outcomes = vec![
+            PolicyOutcome::Pass,
+            PolicyOutcome::Fail {
+                reason: "x".to_string(),
+            },
+        ];
+        assert_eq!(aggregate(&outcomes), ComplianceStatus::PartiallyCompliant);
+    }
+
+    #[test]
+    fn test_not_applicable_is_excluded() {
+        let outcomes = vec![PolicyOutcome::Pass, PolicyOutcome::NotApplicable];
+        assert_eq!(aggregate(&outcomes), ComplianceStatus::Compliant);
+    }
+
+    #[test]
+    fn test_validate_rejects_incoherent_rto() {
+        let result = validate_policy_parameters(Duration::from_secs(8 * 3600), Duration::from_secs(4 * 3600));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::RwLock;
+
+use crate::compliance::{DORAAssessment, NIS2Assessment};
+
+/// The assessment an activity records, distinguished by which framework
+/// produced it — mirrors the two outputs `ComplianceAssessor` already
+/// returns, so provenance doesn't invent a third shape for the payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AssessmentPayload {
+    Dora(DORAAssessment),
+    Nis2(NIS2Assessment),
+}
+
+/// One signed activity in the provenance log: who (the `agent`, i.e. the
+/// assessor identity) assessed what (the `entity`, i.e. `target`) and
+/// when, plus the hash of the previous activity so the activities form a
+/// chain. Named `Activity` to echo the W3C PROV agent/entity/activity
+/// vocabulary the JSON-LD export targets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    pub agent: String,
+    pub entity: String,
+    pub assessment: AssessmentPayload,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub previous_hash: String,
+    pub hash: String,
+    pub signature: String,
+}
+
+/// Canonicalizes the fields that make an activity unique — excluding its
+/// own `hash`/`signature`, which are derived from this digest — and
+/// hashes them with SHA-256.
+fn activity_digest(
+    agent: &str,
+    entity: &str,
+    assessment: &AssessmentPayload,
+    timestamp: &chrono::DateTime<chrono::Utc>,
+    previous_hash: &str,
+) -> Result<[u8; 32]> {
+    #[derive(Serialize)]
+    struct Unsigned<'a> {
+        agent: &'a str,
+        entity: &'a str,
+        assessment: &'a AssessmentPayload,
+        timestamp: &'a chrono::DateTime<chrono::Utc>,
+        previous_hash: &'a str,
+    }
+
+    let canonical = serde_json::to_vec(&Unsigned {
+        agent,
+        entity,
+        assessment,
+        timestamp,
+        previous_hash,
+    })
+    .context("Failed to canonicalize provenance activity")?;
+
+    Ok(Sha256::digest(&canonical).into())
+}
+
+/// The genesis link every chain starts from, so the first activity's
+/// digest includes a `previous_hash` like every other link instead of
+/// needing special-cased verification.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000";
+
+/// Where a broken link was found, if any. `verify_chain` returns this
+/// instead of a bool so an auditor can point straight at the tampered
+/// record instead of re-deriving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainVerification {
+    Intact,
+    BrokenAt { index: usize, reason: String },
+}
+
+/// An append-only, signed log of assessment activities, forming an
+/// Ed25519-backed hash chain: each activity signs its own digest plus
+/// the previous activity's hash, so altering or removing a historical
+/// `ComplianceStatus`/`ThirdPartyRisk` entry invalidates every link
+/// after it.
+pub struct ProvenanceLog {
+    signing_key: SigningKey,
+    activities: RwLock<Vec<Activity>>,
+}
+
+impl ProvenanceLog {
+    pub fn new(signing_key: SigningKey) -> Self {
+        Self {
+            signing_key,
+            activities: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Signs `assessment` and appends it to the chain, linking it to the
+    /// previous activity's hash (or [`GENESIS_HASH`] for the first one).
+    pub fn sign_and_append(
+        &self,
+        agent: &str,
+        entity: &str,
+        assessment: AssessmentPayload,
+    ) -> Result<Activity> {
+        let mut activities = self.activities.write().unwrap();
+        let previous_hash = activities
+            .last()
+            .map(|a| a.hash.clone())
+            .unwrap_or_else(|| GENESIS_HASH.to_string());
+
+        let timestamp = chrono::Utc::now();
+        let digest = activity_digest(agent, entity, &assessment, &timestamp, &previous_hash)?;
+        let signature: Signature = self.signing_key.sign(&digest);
+
+        let activity = Activity {
+            agent: agent.to_string(),
+            entity: entity.to_string(),
+            assessment,
+            timestamp,
+            previous_hash,
+            hash: hex::encode(digest),
+            signature: hex::encode(signature.to_bytes()),
+        };
+
+        activities.push(activity.clone());
+        Ok(activity)
+    }
+
+    /// Walks the chain from genesis, re-deriving each activity's digest
+    /// and checking its signature and its link to the previous hash.
+    /// Returns the first broken link, if any, so auditors know exactly
+    /// where tampering occurred.
+    pub fn verify_chain(&self) -> ChainVerification {
+        let activities = self.activities.read().unwrap();
+        let verifying_key: VerifyingKey = self.signing_key.verifying_key();
+        let mut expected_previous_hash = GENESIS_HASH.to_string();
+
+        for (index, activity) in activities.iter().enumerate() {
+            if activity.previous_hash != expected_previous_hash {
+                return ChainVerification::BrokenAt {
+                    index,
+                    reason: "previous_hash does not match the prior activity's hash".to_string(),
+                };
+            }
+
+            let digest = match activity_digest(
+                &activity.agent,
+                &activity.entity,
+                &activity.assessment,
+                &activity.timestamp,
+                &activity.previous_hash,
+            ) {
+                Ok(digest) => digest,
+                Err(err) => {
+                    return ChainVerification::BrokenAt {
+                        index,
+                        reason: format!("failed to recompute digest: {err}"),
+                    }
+                }
+            };
+
+            if hex::encode(digest) != activity.hash {
+                return ChainVerification::BrokenAt {
+                    index,
+                    reason: "recomputed hash does not match the stored hash".to_string(),
+                };
+            }
+
+            let signature_bytes = match hex::decode(&activity.signature) {
+                Ok(bytes) => bytes,
+                Err(_) => {
+                    return ChainVerification::BrokenAt {
+                        index,
+                        reason: "signature is not valid hex".to_string(),
+                    }
+                }
+            };
+            let signature = match Signature::try_from(signature_bytes.as_slice()) {
+                Ok(signature) => signature,
+                Err(_) => {
+                    return ChainVerification::BrokenAt {
+                        index,
+                        reason: "signature is not a valid Ed25519 signature".to_string(),
+                    }
+                }
+            };
+
+            if verifying_key.verify(&digest, &signature).is_err() {
+                return ChainVerification::BrokenAt {
+                    index,
+                    reason: "signature does not verify against the digest".to_string(),
+                };
+            }
+
+            expected_previous_hash = activity.hash.clone();
+        }
+
+        ChainVerification::Intact
+    }
+
+    /// Exports the chain as a JSON-LD-style document: a `@context`
+    /// mapping onto the W3C PROV vocabulary plus a `@graph` of activities,
+    /// portable enough for an external auditor's own tooling to ingest.
+    pub fn export_jsonld(&self) -> Result<serde_json::Value> {
+        let activities = self.activities.read().unwrap();
+        Ok(serde_json::json!({
+            "@context": {
+                "prov": "http://www.w3.org/ns/prov#",
+                "agent": "prov:agent",
+                "entity": "prov:entity",
+                "assessment": "prov:generated",
+                "previous_hash": "prov:wasInformedBy",
+            },
+            "@graph": activities.iter().collect::<Vec<_>>(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::{ComplianceStatus, DORAAssessment};
+    use std::time::Duration;
+
+    fn sample_assessment() -> AssessmentPayload {
+        AssessmentPayload::Dora(DORAAssessment {
+            ict_risk_score: 0.75,
+            incident_response_time: Duration::from_secs(3600),
+            third_party_risks: Vec::new(),
+            resilience_score: 0.85,
+            compliance_status: ComplianceStatus::Compliant,
+            recommendations: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_chain_of_signed_activities_verifies() {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let log = ProvenanceLog::new(signing_key);
+
+        log.sign_and_append("scorton-assessor", "example.com", sample_assessment())
+            .unwrap();
+        log.sign_and_append("scorton-assessor", "example.com", sample_assessment())
+            .unwrap();
+
+        assert_eq!(log.verify_chain(), ChainVerification::Intact);
+    }
+
+    #[test]
+    fn test_tampering_with_a_historical_entry_breaks_the_chain() {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        let log = ProvenanceLog::new(signing_key);
+
+        log.sign_and_append("scorton-assessor", "example.com", sample_assessment())
+            .unwrap();
+        log.sign_and_append("scorton-assessor", "example.com", sample_assessment())
+            .unwrap();
+
+        {
+            let mut activities = log.activities.write().unwrap();
+            if let AssessmentPayload::Dora(assessment) = &mut activities[0].assessment {
+                assessment.compliance_status = ComplianceStatus::NonCompliant;
+            }
+        }
+
+        match log.verify_chain() {
+            ChainVerification::BrokenAt { index, .. } => assert_eq!(index, 0),
+            ChainVerification::Intact => panic!("expected tampering to break the chain"),
+        }
+    }
+}
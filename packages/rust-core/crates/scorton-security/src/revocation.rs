@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// A single Bloom filter layer: `bits.len()` is sized by
+/// `optimal_bit_count`, and each hash is reduced modulo `bits.len()` into
+/// an index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BloomLayer {
+    bits: Vec<bool>,
+    hash_count: u32,
+}
+
+impl BloomLayer {
+    fn with_capacity(element_count: usize, target_fpr: f64) -> Self {
+        let element_count = element_count.max(1);
+        let size = optimal_bit_count(element_count, target_fpr);
+        let hash_count = optimal_hash_count(size, element_count);
+        Self {
+            bits: vec![false; size],
+            hash_count,
+        }
+    }
+
+    fn insert(&mut self, key: &str) {
+        for idx in self.indices(key) {
+            self.bits[idx] = true;
+        }
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.indices(key).all(|idx| self.bits[idx])
+    }
+
+    /// Double hashing (Kirsch-Mitzenmacher): derive `hash_count` indices from
+    /// two independent hashes instead of hashing the key `hash_count` times.
+    fn indices(&self, key: &str) -> impl Iterator<Item = usize> + '_ {
+        let h1 = hash_with_seed(key, 0);
+        let h2 = hash_with_seed(key, 1);
+        let len = self.bits.len() as u64;
+        (0..self.hash_count).map(move |i| {
+            let combined = h1.wrapping_add((i as u64).wrapping_mul(h2));
+            (combined % len) as usize
+        })
+    }
+}
+
+fn hash_with_seed(key: &str, seed: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn optimal_bit_count(element_count: usize, target_fpr: f64) -> usize {
+    let n = element_count as f64;
+    let m = -(n * target_fpr.ln()) / (std::f64::consts::LN_2.powi(2));
+    (m.ceil() as usize).max(8)
+}
+
+fn optimal_hash_count(bit_count: usize, element_count: usize) -> u32 {
+    let m = bit_count as f64;
+    let n = element_count.max(1) as f64;
+    (((m / n) * std::f64::consts::LN_2).round() as u32).max(1)
+}
+
+/// An offline, CRLite-style revocation check: a cascade of alternating
+/// Bloom filter layers that yields zero false results once built, so a
+/// scan can decide "revoked" without an OCSP round-trip.
+///
+/// Layer 0 is built from the revoked set. Querying an element walks the
+/// layers from 0; the first layer the element is *absent* from decides
+/// membership — absent at an even layer means "not revoked", absent at an
+/// odd layer means "revoked".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationCascade {
+    layers: Vec<BloomLayer>,
+}
+
+impl RevocationCascade {
+    /// Builds the cascade from the revoked ("include") and known-valid
+    /// ("exclude") serial/issuer-SPKI-hash sets, alternating which set
+    /// seeds each layer until a layer produces no false positives.
+    pub fn build(revoked: &[String], valid: &[String], target_fpr: f64) -> Self {
+        let mut layers = Vec::new();
+        let mut include: Vec<String> = revoked.to_vec();
+        let mut exclude: Vec<String> = valid.to_vec();
+
+        loop {
+            let mut layer = BloomLayer::with_capacity(include.len(), target_fpr);
+            for key in &include {
+                layer.insert(key);
+            }
+
+            let false_positives: Vec<String> = exclude
+                .iter()
+                .filter(|key| layer.contains(key))
+                .cloned()
+                .collect();
+
+            layers.push(layer);
+
+            if false_positives.is_empty() {
+                break;
+            }
+
+            // Next layer is seeded by this layer's false positives, with
+            // include/exclude swapped so the cascade alternates.
+            exclude = include;
+            include = false_positives;
+        }
+
+        Self { layers }
+    }
+
+    /// Returns true if `key` (a certificate serial or issuer-SPKI hash)
+    /// is revoked according to the cascade.
+    pub fn is_revoked(&self, key: &str) -> bool {
+        for (depth, layer) in self.layers.iter().enumerate() {
+            if !layer.contains(key) {
+                return depth % 2 == 1;
+            }
+        }
+        // Present in every layer: the key belongs to the set that seeded
+        // the last layer, which is the revoked set when the layer count
+        // is odd.
+        self.layers.len() % 2 == 1
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).context("Failed to serialize revocation cascade")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).context("Failed to deserialize revocation cascade")
+    }
+
+    pub async fn load_from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = tokio::fs::read(path.as_ref())
+            .await
+            .with_context(|| format!("Failed to read revocation cascade from {:?}", path.as_ref()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+static CASCADE_CACHE: OnceLock<Mutex<HashMap<String, (Instant, Arc<RevocationCascade>)>>> = OnceLock::new();
+
+/// Loads the cascade at `path`, reusing the in-memory copy until
+/// `refresh_interval` has elapsed since it was last read from disk — so a
+/// scanner that checks many targets doesn't re-read and re-deserialize a
+/// potentially large cascade file on every single SSL check.
+pub async fn load_cached(path: &str, refresh_interval: Duration) -> Result<Arc<RevocationCascade>> {
+    let cache = CASCADE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+
+    if let Some((loaded_at, cascade)) = cache.lock().unwrap().get(path) {
+        if loaded_at.elapsed() < refresh_interval {
+            return Ok(cascade.clone());
+        }
+    }
+
+    let cascade = Arc::new(RevocationCascade::load_from_path(path).await?);
+    cache
+        .lock()
+        .unwrap()
+        .insert(path.to_string(), (Instant::now(), cascade.clone()));
+    Ok(cascade)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cascade_has_no_false_results() {
+        let revoked: Vec<String> = (0..50).map(|i| format!("revoked-{}", i)).collect();
+        let valid: Vec<String> = (0..50).map(|i| format!("valid-{}", i)).collect();
+
+        let cascade = RevocationCascade::build(&revoked, &valid, 0.01);
+
+        for key in &revoked {
+            assert!(cascade.is_revoked(key));
+        }
+        for key in &valid {
+            assert!(!cascade.is_revoked(key));
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_serialization() {
+        let cascade = RevocationCascade::build(
+            &["revoked-a".to_string()],
+            &["valid-a".to_string()],
+            0.01,
+        );
+        let bytes = cascade.to_bytes().unwrap();
+        let restored = RevocationCascade::from_bytes(&bytes).unwrap();
+        assert!(restored.is_revoked("revoked-a"));
+        assert!(!restored.is_revoked("valid-a"));
+    }
+}
@@ -11,6 +11,13 @@ pub struct ScannerConfig {
     pub max_concurrent: usize,
     pub rate_limit: Option<Duration>,
     pub retry_count: u32,
+    /// Path to a serialized `RevocationCascade` used for offline
+    /// certificate-revocation checks, refreshed on `revocation_refresh_interval`.
+    pub revocation_cascade_path: Option<String>,
+    pub revocation_refresh_interval: Duration,
+    /// Which backend `enumerate_dns` resolves through: the system/
+    /// hickory-resolver pipeline, or a pinned DNS-over-HTTPS resolver.
+    pub resolver: crate::dns::DnsResolverBackend,
 }
 
 impl Default for ScannerConfig {
@@ -20,10 +27,50 @@ impl Default for ScannerConfig {
             max_concurrent: 100,
             rate_limit: Some(Duration::from_millis(100)),
             retry_count: 3,
+            revocation_cascade_path: None,
+            revocation_refresh_interval: Duration::from_secs(6 * 3600),
+            resolver: crate::dns::DnsResolverBackend::default(),
         }
     }
 }
 
+/// A single incremental event emitted while a comprehensive scan runs, so
+/// long scans can stream progress instead of blocking until completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanEvent {
+    pub phase: ScanPhase,
+    pub target: String,
+    pub tool: String,
+    pub status: ScanEventStatus,
+    pub payload: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScanPhase {
+    Started,
+    Finished,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScanEventStatus {
+    Running,
+    Success,
+    Failed(String),
+}
+
+/// `enumerate_dns`'s full result: the raw enumeration (records plus
+/// resolver/transport provenance) plus, where available, the CAA/TLSA/
+/// DNSSEC posture checks that let the comprehensive scan flag real gaps
+/// (e.g. an HTTPS host with no CAA protection, or an unsigned zone).
+/// `posture` is `None` for the DoH backend: it bypasses hickory-resolver
+/// entirely, and the hand-rolled wire client there doesn't implement a
+/// DNSSEC-validating lookup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsEnumerationReport {
+    pub enumeration: crate::dns::DnsEnumeration,
+    pub posture: Option<crate::dns::DnsSecurityPosture>,
+}
+
 pub struct ScannerOrchestrator {
     config: ScannerConfig,
 }
@@ -34,27 +81,56 @@ impl ScannerOrchestrator {
     }
 
     pub async fn run_comprehensive_scan(&self, target: &str) -> Result<HashMap<String, serde_json::Value>> {
-        let mut results = HashMap::new();
-        
-        // Port scan
-        if let Ok(port_results) = self.scan_common_ports(target).await {
-            results.insert("port_scan".to_string(), serde_json::to_value(port_results)?);
-        }
+        let (sender, _receiver) = tokio::sync::broadcast::channel(16);
+        self.run_comprehensive_scan_streaming(target, sender).await
+    }
 
-        // SSL scan
-        if let Ok(ssl_result) = self.scan_ssl(target).await {
-            results.insert("ssl_scan".to_string(), serde_json::to_value(ssl_result)?);
-        }
+    /// Same as [`Self::run_comprehensive_scan`], but pushes a `ScanEvent`
+    /// through `events` as each tool starts and finishes, keyed by `target`,
+    /// so subscribers (a WebSocket handler, a napi callback) get real-time
+    /// progress instead of waiting for the whole scan to complete.
+    pub async fn run_comprehensive_scan_streaming(
+        &self,
+        target: &str,
+        events: tokio::sync::broadcast::Sender<ScanEvent>,
+    ) -> Result<HashMap<String, serde_json::Value>> {
+        let mut results = HashMap::new();
 
-        // DNS enumeration
-        if let Ok(dns_results) = self.enumerate_dns(target).await {
-            results.insert("dns_enum".to_string(), serde_json::to_value(dns_results)?);
+        macro_rules! run_tool {
+            ($tool:expr, $fut:expr) => {{
+                let _ = events.send(ScanEvent {
+                    phase: ScanPhase::Started,
+                    target: target.to_string(),
+                    tool: $tool.to_string(),
+                    status: ScanEventStatus::Running,
+                    payload: None,
+                });
+                let outcome = $fut.await;
+                let (status, payload) = match &outcome {
+                    Ok(value) => (
+                        ScanEventStatus::Success,
+                        serde_json::to_value(value).ok(),
+                    ),
+                    Err(e) => (ScanEventStatus::Failed(e.to_string()), None),
+                };
+                let _ = events.send(ScanEvent {
+                    phase: ScanPhase::Finished,
+                    target: target.to_string(),
+                    tool: $tool.to_string(),
+                    status,
+                    payload: payload.clone(),
+                });
+                if let Ok(_) = &outcome {
+                    results.insert($tool.to_string(), payload.unwrap_or(serde_json::Value::Null));
+                }
+            }};
         }
 
-        // Security headers
-        if let Ok(headers) = self.check_security_headers(target).await {
-            results.insert("security_headers".to_string(), serde_json::to_value(headers)?);
-        }
+        run_tool!("port_scan", self.scan_common_ports(target));
+        run_tool!("ssl_scan", self.scan_ssl(target));
+        run_tool!("ssl_vulnerabilities", self.scan_ssl_vulnerabilities(target));
+        run_tool!("dns_enum", self.enumerate_dns(target));
+        run_tool!("security_headers", self.check_security_headers(target));
 
         Ok(results)
     }
@@ -73,9 +149,59 @@ impl ScannerOrchestrator {
         scanner.ssl_scan(target, 443).await
     }
 
-    async fn enumerate_dns(&self, target: &str) -> Result<Vec<crate::DNSRecord>> {
-        let scanner = crate::SecurityScanner::new(self.config.timeout, self.config.max_concurrent);
-        scanner.dns_enum(target).await
+    /// Same certificate vulnerability checks as [`crate::ssl::check_ssl_vulnerabilities`],
+    /// but consulting the offline `RevocationCascade` at
+    /// `ScannerConfig.revocation_cascade_path` (reloaded at most every
+    /// `revocation_refresh_interval`) so a revoked certificate is flagged
+    /// without an OCSP round-trip.
+    async fn scan_ssl_vulnerabilities(&self, target: &str) -> Result<Vec<crate::ssl::SslVulnerability>> {
+        let cascade = match &self.config.revocation_cascade_path {
+            Some(path) => Some(
+                crate::revocation::load_cached(path, self.config.revocation_refresh_interval).await?,
+            ),
+            None => None,
+        };
+
+        crate::ssl::check_ssl_vulnerabilities_with_revocation(target, 443, cascade.as_deref()).await
+    }
+
+    /// Returns a [`DnsEnumerationReport`] rather than a bare
+    /// `Vec<DNSRecord>` so the result carries both which resolver/
+    /// transport actually answered, and (for the System backend) the
+    /// CAA/TLSA/DNSSEC posture the comprehensive scan needs to flag gaps
+    /// like an HTTPS host with no CAA protection or an unsigned zone.
+    async fn enumerate_dns(&self, target: &str) -> Result<DnsEnumerationReport> {
+        match &self.config.resolver {
+            crate::dns::DnsResolverBackend::System => {
+                let resolver_config = crate::dns::ResolverConfig {
+                    timeout: self.config.timeout,
+                    retry_count: self.config.retry_count as usize,
+                    ..Default::default()
+                };
+                let enumeration =
+                    crate::dns::enumerate_dns_records_with_resolver(target, &resolver_config).await?;
+                let resolver = crate::dns::build_resolver(&resolver_config)?;
+                let posture =
+                    crate::dns::assess_dns_security_posture(&resolver, target, &enumeration.records).await;
+                Ok(DnsEnumerationReport {
+                    enumeration,
+                    posture: Some(posture),
+                })
+            }
+            crate::dns::DnsResolverBackend::DoH { endpoint } => {
+                let enumeration = crate::dns::enumerate_dns_records_doh(
+                    target,
+                    endpoint,
+                    self.config.timeout,
+                    self.config.retry_count as usize,
+                )
+                .await?;
+                Ok(DnsEnumerationReport {
+                    enumeration,
+                    posture: None,
+                })
+            }
+        }
     }
 
     async fn check_security_headers(&self, target: &str) -> Result<crate::SecurityHeaders> {
@@ -1,39 +1,205 @@
 use serde::{Deserialize, Serialize};
-use anyhow::{Result, Context};
+use anyhow::{Result, Context, anyhow};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpStream;
 use tokio::time::timeout;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::{self, pki_types::ServerName};
+use x509_parser::prelude::*;
 
+use crate::KeyType;
+use crate::revocation::RevocationCascade;
+
+/// Performs a real TLS handshake against `target:port` and parses the leaf
+/// and intermediate certificates out of the negotiated chain, trusting the
+/// public Web PKI roots.
 pub async fn analyze_ssl_certificate(target: &str, port: u16) -> Result<crate::SSLCertificate> {
+    let mut root_store = rustls::RootCertStore::empty();
+    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    analyze_ssl_certificate_with_roots(target, port, root_store).await
+}
+
+/// Same as [`analyze_ssl_certificate`], but validates the handshake against
+/// `root_store` instead of the public Web PKI roots — lets tests point it
+/// at a self-signed certificate a local harness trusts, without weakening
+/// the real entry point's validation.
+pub async fn analyze_ssl_certificate_with_roots(
+    target: &str,
+    port: u16,
+    root_store: rustls::RootCertStore,
+) -> Result<crate::SSLCertificate> {
     let socket_addr = format!("{}:{}", target, port);
-    
-    // Connect to the target
+
     let stream = timeout(Duration::from_secs(10), TcpStream::connect(&socket_addr))
         .await
         .context("Connection timeout")?
         .context("Failed to connect")?;
 
-    // In a real implementation, you would use rustls or openssl to analyze the certificate
-    // For now, we'll return a placeholder certificate
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    let connector = TlsConnector::from(Arc::new(config));
+
+    let server_name = ServerName::try_from(target.to_string())
+        .map_err(|_| anyhow!("Invalid DNS name: {}", target))?;
+
+    let tls_stream = timeout(Duration::from_secs(10), connector.connect(server_name, stream))
+        .await
+        .context("TLS handshake timeout")?
+        .context("TLS handshake failed")?;
+
+    let (_, connection) = tls_stream.get_ref();
+    let der_chain = connection
+        .peer_certificates()
+        .context("Server presented no certificates")?;
+
+    let (leaf_der, intermediate_ders) = der_chain
+        .split_first()
+        .context("Empty certificate chain")?;
+
+    let leaf = parse_leaf(leaf_der)?;
+    let chain = intermediate_ders
+        .iter()
+        .filter_map(|der| parse_intermediate(der).ok())
+        .collect();
+
     Ok(crate::SSLCertificate {
-        subject: format!("CN={}", target),
-        issuer: "Placeholder CA".to_string(),
-        valid_from: chrono::Utc::now() - chrono::Duration::days(365),
-        valid_until: chrono::Utc::now() + chrono::Duration::days(365),
-        signature_algorithm: "SHA256-RSA".to_string(),
-        key_size: 2048,
-        serial_number: "1234567890".to_string(),
-        san: vec![target.to_string()],
+        chain,
+        ..leaf
     })
 }
 
+fn parse_leaf(der: &rustls::pki_types::CertificateDer<'_>) -> Result<crate::SSLCertificate> {
+    let (_, cert) = X509Certificate::from_der(der.as_ref())
+        .map_err(|e| anyhow!("Failed to parse leaf certificate: {}", e))?;
+
+    let (key_type, key_size, curve) = describe_public_key(&cert);
+
+    Ok(crate::SSLCertificate {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        valid_from: asn1_time_to_chrono(cert.validity().not_before),
+        valid_until: asn1_time_to_chrono(cert.validity().not_after),
+        signature_algorithm: signature_algorithm_name(&cert),
+        key_size,
+        serial_number: cert.raw_serial_as_string(),
+        san: subject_alt_names(&cert),
+        key_type,
+        curve,
+        chain: Vec::new(),
+    })
+}
+
+fn parse_intermediate(der: &rustls::pki_types::CertificateDer<'_>) -> Result<crate::ChainCertificate> {
+    let (_, cert) = X509Certificate::from_der(der.as_ref())
+        .map_err(|e| anyhow!("Failed to parse intermediate certificate: {}", e))?;
+    let (_, key_size, _) = describe_public_key(&cert);
+
+    Ok(crate::ChainCertificate {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        signature_algorithm: signature_algorithm_name(&cert),
+        key_size,
+    })
+}
+
+fn describe_public_key(cert: &X509Certificate<'_>) -> (KeyType, u32, Option<String>) {
+    let spki = cert.public_key();
+    match &spki.parsed() {
+        Ok(PublicKey::RSA(rsa)) => (KeyType::Rsa, (rsa.key_size() as u32) * 8, None),
+        Ok(PublicKey::EC(ec)) => {
+            let curve = spki
+                .algorithm
+                .parameters_oid()
+                .ok()
+                .map(|oid| {
+                    oid_registry::oid2sn(&oid)
+                        .map(|sn| sn.to_string())
+                        .unwrap_or_else(|_| oid.to_id_string())
+                })
+                .unwrap_or_else(|| "unknown".to_string());
+            (KeyType::Ecdsa, (ec.key_size() as u32) * 8, Some(curve))
+        }
+        Ok(PublicKey::Unknown(_)) | Err(_) => {
+            if cert.public_key().algorithm.algorithm == oid_registry::OID_SIG_ED25519 {
+                (KeyType::Ed25519, 256, Some("Ed25519".to_string()))
+            } else {
+                (KeyType::Unknown, 0, None)
+            }
+        }
+        _ => (KeyType::Unknown, 0, None),
+    }
+}
+
+/// The algorithm's short name (e.g. `sha1WithRSAEncryption`) so the
+/// weak-algorithm check below can match on it, falling back to the
+/// dotted OID string when `oid_registry` doesn't recognize it.
+fn signature_algorithm_name(cert: &X509Certificate<'_>) -> String {
+    let oid = &cert.signature_algorithm.algorithm;
+    oid_registry::oid2sn(oid)
+        .map(|sn| sn.to_string())
+        .unwrap_or_else(|_| oid.to_id_string())
+}
+
+fn subject_alt_names(cert: &X509Certificate<'_>) -> Vec<String> {
+    cert.extensions()
+        .iter()
+        .find_map(|ext| match ext.parsed_extension() {
+            ParsedExtension::SubjectAlternativeName(san) => Some(
+                san.general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(dns) => Some(dns.to_string()),
+                        GeneralName::IPAddress(ip) => Some(format!("{:?}", ip)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+fn asn1_time_to_chrono(t: ASN1Time) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::from_timestamp(t.timestamp(), 0).unwrap_or_else(chrono::Utc::now)
+}
+
+/// Signature algorithms and curves known to be deprecated or unsafe, used
+/// alongside the expiry/key-size heuristics already performed here.
+const WEAK_SIGNATURE_ALGORITHMS: &[&str] = &["sha1WithRSAEncryption", "md5WithRSAEncryption"];
+const WEAK_CURVES: &[&str] = &["secp192r1", "secp160r1"];
+
 pub async fn check_ssl_vulnerabilities(target: &str, port: u16) -> Result<Vec<SslVulnerability>> {
+    check_ssl_vulnerabilities_with_revocation(target, port, None).await
+}
+
+/// Same as [`check_ssl_vulnerabilities`], but additionally consults an
+/// offline `RevocationCascade` (CRLite-style) so a revoked certificate is
+/// flagged without an OCSP round-trip.
+pub async fn check_ssl_vulnerabilities_with_revocation(
+    target: &str,
+    port: u16,
+    revocation: Option<&RevocationCascade>,
+) -> Result<Vec<SslVulnerability>> {
     let mut vulnerabilities = Vec::new();
-    
-    // Check for common SSL vulnerabilities
+
     let cert = analyze_ssl_certificate(target, port).await?;
-    
-    // Check certificate expiration
+
+    if let Some(cascade) = revocation {
+        if cascade.is_revoked(&cert.serial_number) {
+            vulnerabilities.push(SslVulnerability {
+                name: "Certificate Revoked".to_string(),
+                severity: VulnerabilitySeverity::Critical,
+                description: format!(
+                    "Serial {} is present in the revocation cascade",
+                    cert.serial_number
+                ),
+                recommendation: "Stop trusting this certificate and reissue it".to_string(),
+            });
+        }
+    }
+
     if cert.valid_until < chrono::Utc::now() + chrono::Duration::days(30) {
         vulnerabilities.push(SslVulnerability {
             name: "Certificate Expiring Soon".to_string(),
@@ -42,9 +208,8 @@ pub async fn check_ssl_vulnerabilities(target: &str, port: u16) -> Result<Vec<Ss
             recommendation: "Renew certificate before expiration".to_string(),
         });
     }
-    
-    // Check key size
-    if cert.key_size < 2048 {
+
+    if matches!(cert.key_type, KeyType::Rsa) && cert.key_size < 2048 {
         vulnerabilities.push(SslVulnerability {
             name: "Weak Key Size".to_string(),
             severity: VulnerabilitySeverity::High,
@@ -52,7 +217,33 @@ pub async fn check_ssl_vulnerabilities(target: &str, port: u16) -> Result<Vec<Ss
             recommendation: "Upgrade to at least 2048-bit key".to_string(),
         });
     }
-    
+
+    if WEAK_SIGNATURE_ALGORITHMS
+        .iter()
+        .any(|weak| cert.signature_algorithm.contains(weak))
+    {
+        vulnerabilities.push(SslVulnerability {
+            name: "Deprecated Signature Algorithm".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: format!(
+                "Certificate is signed with {}, which is deprecated",
+                cert.signature_algorithm
+            ),
+            recommendation: "Reissue the certificate with SHA-256 or stronger".to_string(),
+        });
+    }
+
+    if let Some(curve) = &cert.curve {
+        if WEAK_CURVES.iter().any(|weak| curve.contains(weak)) {
+            vulnerabilities.push(SslVulnerability {
+                name: "Weak Elliptic Curve".to_string(),
+                severity: VulnerabilitySeverity::High,
+                description: format!("Certificate uses the weak curve {}", curve),
+                recommendation: "Use P-256 or stronger".to_string(),
+            });
+        }
+    }
+
     Ok(vulnerabilities)
 }
 
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+
+/// A key a [`ComplianceStore`] can be written/read/deleted by. Assessments
+/// are keyed by `(target, framework, timestamp)` so trend queries can ask
+/// "how has this score moved over the last N assessments?" instead of
+/// treating each run as stateless.
+pub trait Key: Clone + Eq + Hash + Send + Sync {}
+impl<T: Clone + Eq + Hash + Send + Sync> Key for T {}
+
+/// Anything a [`ComplianceStore`] can persist.
+pub trait Writable: Clone + Send + Sync {}
+impl<T: Clone + Send + Sync> Writable for T {}
+
+/// Governs what happens to a cached prior value when a fresh write
+/// arrives for the same key: `Overwrite` replaces it (for trend data
+/// where the latest point should win), `Remove` evicts it
+/// instead so the next read falls through to the backing store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheUpdatePolicy {
+    Overwrite,
+    Remove,
+}
+
+/// Storage layer for historical `DORAAssessment`/`NIS2Assessment` values,
+/// generic over a key type so the same trait serves both frameworks.
+pub trait ComplianceStore<K: Key, V: Writable> {
+    fn write(&self, key: K, value: V) -> Result<()>;
+    fn read(&self, key: &K) -> Result<Option<V>>;
+    fn delete(&self, key: &K) -> Result<()>;
+    /// All values currently stored, in insertion order, for trend queries.
+    fn history(&self) -> Result<Vec<(K, V)>>;
+}
+
+/// An in-memory write-through cache in front of a `ComplianceStore`,
+/// governed by a [`CacheUpdatePolicy`] so callers control whether a fresh
+/// assessment evicts or updates the cached prior. `persisted` is the
+/// durable, policy-independent record of the latest value per key;
+/// `entries` is the fast-path cache `read()` tries first. Under
+/// `Overwrite`, a write populates both, so the next read is a cache hit.
+/// Under `Remove`, a write evicts `entries` for that key, so the next
+/// read has to fall through to `persisted` instead.
+pub struct CachedComplianceStore<K: Key, V: Writable> {
+    policy: CacheUpdatePolicy,
+    entries: RwLock<HashMap<K, V>>,
+    persisted: RwLock<HashMap<K, V>>,
+    order: RwLock<Vec<K>>,
+}
+
+impl<K: Key, V: Writable> CachedComplianceStore<K, V> {
+    pub fn new(policy: CacheUpdatePolicy) -> Self {
+        Self {
+            policy,
+            entries: RwLock::new(HashMap::new()),
+            persisted: RwLock::new(HashMap::new()),
+            order: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl<K: Key, V: Writable> ComplianceStore<K, V> for CachedComplianceStore<K, V> {
+    fn write(&self, key: K, value: V) -> Result<()> {
+        let mut persisted = self.persisted.write().unwrap();
+        let is_new = !persisted.contains_key(&key);
+        persisted.insert(key.clone(), value.clone());
+        drop(persisted);
+
+        match self.policy {
+            CacheUpdatePolicy::Overwrite => {
+                self.entries.write().unwrap().insert(key.clone(), value);
+            }
+            CacheUpdatePolicy::Remove => {
+                self.entries.write().unwrap().remove(&key);
+            }
+        }
+
+        if is_new {
+            self.order.write().unwrap().push(key);
+        }
+
+        Ok(())
+    }
+
+    fn read(&self, key: &K) -> Result<Option<V>> {
+        if let Some(value) = self.entries.read().unwrap().get(key).cloned() {
+            return Ok(Some(value));
+        }
+        Ok(self.persisted.read().unwrap().get(key).cloned())
+    }
+
+    fn delete(&self, key: &K) -> Result<()> {
+        self.entries.write().unwrap().remove(key);
+        self.persisted.write().unwrap().remove(key);
+        self.order.write().unwrap().retain(|k| k != key);
+        Ok(())
+    }
+
+    fn history(&self) -> Result<Vec<(K, V)>> {
+        let persisted = self.persisted.read().unwrap();
+        let order = self.order.read().unwrap();
+        Ok(order
+            .iter()
+            .filter_map(|key| persisted.get(key).map(|value| (key.clone(), value.clone())))
+            .collect())
+    }
+}
+
+/// Key for a single historical assessment: which target, which
+/// regulatory framework, and when it was produced.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AssessmentKey {
+    pub target: String,
+    pub framework: AssessmentFramework,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssessmentFramework {
+    Dora,
+    Nis2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overwrite_policy_replaces_prior_value() {
+        let store: CachedComplianceStore<AssessmentKey, f64> =
+            CachedComplianceStore::new(CacheUpdatePolicy::Overwrite);
+        let key = AssessmentKey {
+            target: "example.com".to_string(),
+            framework: AssessmentFramework::Dora,
+            timestamp: 1,
+        };
+
+        store.write(key.clone(), 0.5).unwrap();
+        store.write(key.clone(), 0.9).unwrap();
+
+        assert_eq!(store.read(&key).unwrap(), Some(0.9));
+        assert_eq!(store.history().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_history_detects_regression() {
+        let store: CachedComplianceStore<AssessmentKey, f64> =
+            CachedComplianceStore::new(CacheUpdatePolicy::Overwrite);
+
+        for (i, score) in [0.9, 0.8, 0.5].into_iter().enumerate() {
+            let key = AssessmentKey {
+                target: "example.com".to_string(),
+                framework: AssessmentFramework::Dora,
+                timestamp: i as i64,
+            };
+            store.write(key, score).unwrap();
+        }
+
+        let history = store.history().unwrap();
+        let scores: Vec<f64> = history.iter().map(|(_, score)| *score).collect();
+        assert_eq!(scores, vec![0.9, 0.8, 0.5]);
+    }
+
+    #[test]
+    fn test_remove_policy_evicts_cache_but_read_still_falls_through() {
+        let store: CachedComplianceStore<AssessmentKey, f64> =
+            CachedComplianceStore::new(CacheUpdatePolicy::Remove);
+        let key = AssessmentKey {
+            target: "example.com".to_string(),
+            framework: AssessmentFramework::Dora,
+            timestamp: 1,
+        };
+
+        store.write(key.clone(), 0.5).unwrap();
+
+        // The fast-path cache was evicted, but the value is still
+        // retrievable (and visible in history) via the durable layer —
+        // unlike `Overwrite`, it just isn't served from memory.
+        assert!(store.entries.read().unwrap().get(&key).is_none());
+        assert_eq!(store.read(&key).unwrap(), Some(0.5));
+        assert_eq!(store.history().unwrap(), vec![(key, 0.5)]);
+    }
+}
@@ -0,0 +1,223 @@
+//! In-process HTTP/TLS fixtures for scanner tests, so assertions run
+//! against a server this crate's own tests control (`127.0.0.1:<port>`)
+//! instead of a live host like `example.com` — exact outcomes instead of
+//! `is_ok() || is_err()`, and failure paths (a closed port, a missing
+//! header, a mid-chain redirect) exercisable on demand.
+//!
+//! Test-only: not part of the crate's public API.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+/// One canned HTTP response [`MockHttpServer`] serves to every connection
+/// it accepts — the header/redirect tests only care about the response
+/// side, so the request itself is read and discarded.
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    status: u16,
+    status_text: String,
+    headers: Vec<(String, String)>,
+}
+
+impl MockResponse {
+    pub fn ok() -> Self {
+        Self {
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// A redirect response whose `Location` is `target` (absolute or
+    /// relative — `headers::resolve_redirect_location` resolves either).
+    pub fn redirect(status: u16, target: &str) -> Self {
+        Self {
+            status,
+            status_text: "Found".to_string(),
+            headers: vec![("Location".to_string(), target.to_string())],
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut response = format!("HTTP/1.1 {} {}\r\n", self.status, self.status_text);
+        for (name, value) in &self.headers {
+            response.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        response.push_str("content-length: 0\r\nconnection: close\r\n\r\n");
+        response.into_bytes()
+    }
+}
+
+/// A minimal in-process HTTP/1.1 server bound to an ephemeral `127.0.0.1`
+/// port, replying with the same [`MockResponse`] to every request.
+pub struct MockHttpServer {
+    addr: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl MockHttpServer {
+    pub async fn start(response: MockResponse) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock HTTP server");
+        let addr = listener.local_addr().expect("mock HTTP server local addr");
+
+        let handle = tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let response = response.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    // Best-effort: a closed/reset read just means the
+                    // client already disconnected before this accept.
+                    let _ = stream.read(&mut buf).await;
+                    let _ = stream.write_all(&response.to_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Self { addr, handle }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for MockHttpServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// A minimal in-process TLS server presenting a freshly generated
+/// self-signed certificate for `127.0.0.1`, so `ssl_scan` tests can
+/// assert on a certificate this crate's own tests control. Exposes a
+/// [`rustls::RootCertStore`] trusting exactly that certificate, for use
+/// with [`crate::ssl::analyze_ssl_certificate_with_roots`].
+pub struct MockTlsServer {
+    addr: SocketAddr,
+    handle: JoinHandle<()>,
+    pub root_store: rustls::RootCertStore,
+    /// The `CN` the self-signed leaf certificate was issued for.
+    pub common_name: String,
+}
+
+impl MockTlsServer {
+    pub async fn start() -> Self {
+        let common_name = "mock-harness.test".to_string();
+
+        let mut params = rcgen::CertificateParams::new(vec!["127.0.0.1".to_string()])
+            .expect("build certificate params");
+        params.distinguished_name = rcgen::DistinguishedName::new();
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, common_name.clone());
+        let key_pair = rcgen::KeyPair::generate().expect("generate TLS key pair");
+        let cert = params
+            .self_signed(&key_pair)
+            .expect("self-sign mock TLS certificate");
+
+        let cert_der = cert.der().clone();
+        let key_der = PrivateKeyDer::try_from(key_pair.serialize_der())
+            .expect("encode mock TLS private key");
+
+        let mut root_store = rustls::RootCertStore::empty();
+        root_store
+            .add(cert_der.clone())
+            .expect("trust mock TLS certificate");
+
+        let server_config = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert_der], key_der)
+            .expect("build mock TLS server config");
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(server_config));
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock TLS server");
+        let addr = listener.local_addr().expect("mock TLS server local addr");
+
+        let handle = tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    // `analyze_ssl_certificate` only needs the handshake
+                    // to complete; there's no application data to serve.
+                    let _ = acceptor.accept(stream).await;
+                });
+            }
+        });
+
+        Self {
+            addr,
+            handle,
+            root_store,
+            common_name,
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+}
+
+impl Drop for MockTlsServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// A bare TCP listener on an ephemeral `127.0.0.1` port that accepts and
+/// immediately drops connections — enough for `port_scan` to observe the
+/// port as open, without speaking any particular protocol on it.
+pub struct MockTcpListener {
+    addr: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl MockTcpListener {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock TCP listener");
+        let addr = listener.local_addr().expect("mock TCP listener local addr");
+
+        let handle = tokio::spawn(async move {
+            loop {
+                let Ok((_stream, _)) = listener.accept().await else {
+                    break;
+                };
+            }
+        });
+
+        Self { addr, handle }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+}
+
+impl Drop for MockTcpListener {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::{Context, Result};
+use k256::schnorr::SigningKey;
+use std::path::Path;
+
+/// Loads (or generates, on first run) the Schnorr key this server signs
+/// compliance attestations with, mirroring `JwtKeyManager`'s
+/// load-or-generate pattern for asymmetric JWT keys. With no path
+/// configured, a fresh key is generated per process — fine for a
+/// single-run attestation, but a persistent path is required for
+/// attestations to remain verifiable against the same signer across
+/// restarts.
+pub fn load_or_generate_signing_key(key_path: Option<&Path>) -> Result<SigningKey> {
+    let Some(path) = key_path else {
+        return Ok(SigningKey::random(&mut rand::thread_rng()));
+    };
+
+    if path.exists() {
+        let hex_key = std::fs::read_to_string(path).context("Failed to read compliance signing key")?;
+        let bytes = hex::decode(hex_key.trim()).context("Compliance signing key is not valid hex")?;
+        return SigningKey::from_bytes(&bytes).context("Invalid compliance signing key");
+    }
+
+    let signing_key = SigningKey::random(&mut rand::thread_rng());
+    std::fs::write(path, hex::encode(signing_key.to_bytes()))
+        .context("Failed to write compliance signing key")?;
+    Ok(signing_key)
+}
@@ -1,25 +1,110 @@
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+use anyhow::{bail, Result};
+
+/// Which algorithm the server signs/verifies JWTs with. `Hs256` is kept
+/// for backward compatibility with existing symmetric-secret deployments;
+/// `Rs256`/`EdDsa` sign with an asymmetric keypair so only the public key
+/// needs to be shared with API consumers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum JwtAlgorithm {
+    Hs256,
+    Rs256,
+    EdDsa,
+}
+
+impl JwtAlgorithm {
+    fn from_env_str(s: &str) -> Option<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "HS256" => Some(JwtAlgorithm::Hs256),
+            "RS256" => Some(JwtAlgorithm::Rs256),
+            "EDDSA" => Some(JwtAlgorithm::EdDsa),
+            _ => None,
+        }
+    }
+}
+
+const INSECURE_DEFAULT_SECRET: &str = "default-secret-key";
 
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    pub jwt_algorithm: JwtAlgorithm,
+    /// Only used when `jwt_algorithm` is `Hs256`.
     pub jwt_secret: String,
+    /// Only used when `jwt_algorithm` is `Rs256`/`EdDsa`. If the files
+    /// don't exist yet, a keypair is generated on first run and written
+    /// here; only the public key ever needs to leave the server.
+    pub jwt_private_key_path: Option<PathBuf>,
+    pub jwt_public_key_path: Option<PathBuf>,
+    /// When set, `AuthMiddleware` verifies against this JWKS endpoint's
+    /// published keys (selected per-token by `kid`) instead of a fixed
+    /// key, refreshing its cache every `jwt_jwks_refresh_interval`.
+    pub jwt_jwks_url: Option<String>,
+    pub jwt_jwks_refresh_interval: Duration,
+    pub jwt_token_ttl: Duration,
     pub cors_origins: Vec<String>,
     pub rate_limit: u32,
+    /// Trades scan depth for speed on every compliance assessment this
+    /// server runs; see `scorton_security::compliance::AssessmentMode`.
+    pub assessment_mode: scorton_security::compliance::AssessmentMode,
+    /// Where the Schnorr key signing attestations (see
+    /// `/api/compliance/attested`) is persisted. Generated on first run
+    /// if the file doesn't exist yet; `None` generates a fresh key every
+    /// process start, which only makes sense for short-lived testing.
+    pub compliance_signing_key_path: Option<PathBuf>,
 }
 
 impl ServerConfig {
-    pub fn from_env() -> Self {
-        Self {
+    /// Builds the config from the environment, refusing to start with the
+    /// insecure default HS256 secret unless `SCORTON_ALLOW_INSECURE_JWT`
+    /// is explicitly set.
+    pub fn from_env() -> Result<Self> {
+        let jwt_algorithm = env::var("SCORTON_JWT_ALGORITHM")
+            .ok()
+            .and_then(|s| JwtAlgorithm::from_env_str(&s))
+            .unwrap_or(JwtAlgorithm::Hs256);
+
+        let jwt_secret = env::var("SCORTON_JWT_SECRET")
+            .unwrap_or_else(|_| INSECURE_DEFAULT_SECRET.to_string());
+
+        let allow_insecure = env::var("SCORTON_ALLOW_INSECURE_JWT")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        if jwt_algorithm == JwtAlgorithm::Hs256 && jwt_secret == INSECURE_DEFAULT_SECRET && !allow_insecure {
+            bail!(
+                "refusing to start with the default HS256 JWT secret; set SCORTON_JWT_SECRET \
+                 or opt into asymmetric keys, or set SCORTON_ALLOW_INSECURE_JWT=1 to override"
+            );
+        }
+
+        Ok(Self {
             host: env::var("SCORTON_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
             port: env::var("SCORTON_PORT")
                 .unwrap_or_else(|_| "8000".to_string())
                 .parse()
                 .unwrap_or(8000),
-            jwt_secret: env::var("SCORTON_JWT_SECRET")
-                .unwrap_or_else(|_| "default-secret-key".to_string()),
+            jwt_algorithm,
+            jwt_secret,
+            jwt_private_key_path: env::var("SCORTON_JWT_PRIVATE_KEY_PATH").ok().map(PathBuf::from),
+            jwt_public_key_path: env::var("SCORTON_JWT_PUBLIC_KEY_PATH").ok().map(PathBuf::from),
+            jwt_jwks_url: env::var("SCORTON_JWT_JWKS_URL").ok(),
+            jwt_jwks_refresh_interval: Duration::from_secs(
+                env::var("SCORTON_JWT_JWKS_REFRESH_SECS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .unwrap_or(300),
+            ),
+            jwt_token_ttl: Duration::from_secs(
+                env::var("SCORTON_JWT_TTL_SECS")
+                    .unwrap_or_else(|_| "3600".to_string())
+                    .parse()
+                    .unwrap_or(3600),
+            ),
             cors_origins: env::var("SCORTON_CORS_ORIGINS")
                 .unwrap_or_else(|_| "*".to_string())
                 .split(',')
@@ -29,7 +114,14 @@ impl ServerConfig {
                 .unwrap_or_else(|_| "1000".to_string())
                 .parse()
                 .unwrap_or(1000),
-        }
+            assessment_mode: env::var("SCORTON_ASSESSMENT_MODE")
+                .ok()
+                .and_then(|s| scorton_security::compliance::AssessmentMode::from_env_str(&s))
+                .unwrap_or_default(),
+            compliance_signing_key_path: env::var("SCORTON_COMPLIANCE_SIGNING_KEY_PATH")
+                .ok()
+                .map(PathBuf::from),
+        })
     }
 }
 
@@ -38,9 +130,17 @@ impl Default for ServerConfig {
         Self {
             host: "127.0.0.1".to_string(),
             port: 8000,
-            jwt_secret: "default-secret-key".to_string(),
+            jwt_algorithm: JwtAlgorithm::Hs256,
+            jwt_secret: INSECURE_DEFAULT_SECRET.to_string(),
+            jwt_private_key_path: None,
+            jwt_public_key_path: None,
+            jwt_jwks_url: None,
+            jwt_jwks_refresh_interval: Duration::from_secs(300),
+            jwt_token_ttl: Duration::from_secs(3600),
             cors_origins: vec!["*".to_string()],
             rate_limit: 1000,
+            assessment_mode: scorton_security::compliance::AssessmentMode::default(),
+            compliance_signing_key_path: None,
         }
     }
 }
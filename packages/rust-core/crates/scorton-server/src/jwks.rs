@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use jsonwebtoken::jwk::AlgorithmParameters;
+use jsonwebtoken::{Algorithm, DecodingKey};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Caches a JWKS endpoint's published keys by `kid`, so `AuthMiddleware`
+/// doesn't fetch the key set on every request. Refreshes on a cache miss
+/// (a new `kid` the cache hasn't seen yet, e.g. right after the issuer
+/// rotates) or once `refresh_interval` has elapsed, whichever comes
+/// first — key rotation on the identity provider's side never requires a
+/// server restart.
+pub struct JwksKeyStore {
+    jwks_url: String,
+    refresh_interval: Duration,
+    http_client: reqwest::Client,
+    cache: RwLock<JwksCache>,
+}
+
+#[derive(Default)]
+struct JwksCache {
+    keys: HashMap<String, (Algorithm, DecodingKey)>,
+    last_refreshed: Option<Instant>,
+}
+
+impl JwksKeyStore {
+    pub fn new(jwks_url: String, refresh_interval: Duration) -> Self {
+        Self {
+            jwks_url,
+            refresh_interval,
+            http_client: reqwest::Client::new(),
+            cache: RwLock::new(JwksCache::default()),
+        }
+    }
+
+    /// Returns the algorithm and decoding key published under `kid`,
+    /// refreshing the cache first if `kid` is unknown or the cache has
+    /// gone stale.
+    pub async fn get_key(&self, kid: &str) -> Result<(Algorithm, DecodingKey)> {
+        if !self.needs_refresh() {
+            if let Some(key) = self.cached_key(kid) {
+                return Ok(key);
+            }
+        }
+
+        self.refresh().await?;
+
+        self.cached_key(kid)
+            .with_context(|| format!("No JWKS key found for kid '{kid}'"))
+    }
+
+    fn cached_key(&self, kid: &str) -> Option<(Algorithm, DecodingKey)> {
+        self.cache.read().unwrap().keys.get(kid).cloned()
+    }
+
+    fn needs_refresh(&self) -> bool {
+        match self.cache.read().unwrap().last_refreshed {
+            Some(last_refreshed) => last_refreshed.elapsed() >= self.refresh_interval,
+            None => true,
+        }
+    }
+
+    async fn refresh(&self) -> Result<()> {
+        let jwk_set: jsonwebtoken::jwk::JwkSet = self
+            .http_client
+            .get(&self.jwks_url)
+            .send()
+            .await
+            .context("Failed to fetch JWKS")?
+            .json()
+            .await
+            .context("JWKS response was not valid JSON")?;
+
+        let mut keys = HashMap::new();
+        for jwk in jwk_set.keys {
+            let Some(kid) = jwk.common.key_id.clone() else {
+                continue;
+            };
+            match decoding_key_from_jwk(&jwk) {
+                Ok(decoded) => {
+                    keys.insert(kid, decoded);
+                }
+                Err(e) => {
+                    // Don't let one malformed/unsupported entry take down
+                    // the whole refresh, but don't swallow it either —
+                    // every token signed with this kid will otherwise
+                    // fail with an opaque "unknown signing key" 401 and
+                    // nothing here will explain why.
+                    eprintln!("Skipping unusable JWKS key '{kid}': {e:#}");
+                }
+            }
+        }
+
+        let mut cache = self.cache.write().unwrap();
+        cache.keys = keys;
+        cache.last_refreshed = Some(Instant::now());
+        Ok(())
+    }
+}
+
+/// Builds a decoding key from one JWKS entry, supporting the asymmetric
+/// algorithms `AuthMiddleware` already knows how to verify: RSA (RS256),
+/// Ed25519 (EdDSA) as an OKP key, and P-256 EC keys (ES256) — identity
+/// providers that sign with ES256 publish this key type.
+fn decoding_key_from_jwk(jwk: &jsonwebtoken::jwk::Jwk) -> Result<(Algorithm, DecodingKey)> {
+    match &jwk.algorithm {
+        AlgorithmParameters::RSA(rsa) => {
+            let key = DecodingKey::from_rsa_components(&rsa.n, &rsa.e)
+                .context("Invalid RSA JWKS key")?;
+            Ok((Algorithm::RS256, key))
+        }
+        AlgorithmParameters::OctetKeyPair(okp) => {
+            let key =
+                DecodingKey::from_ed_components(&okp.x).context("Invalid Ed25519 JWKS key")?;
+            Ok((Algorithm::EdDSA, key))
+        }
+        AlgorithmParameters::EllipticCurve(ec) => {
+            if ec.curve != jsonwebtoken::jwk::EllipticCurve::P256 {
+                anyhow::bail!(
+                    "Unsupported JWKS EC curve: {:?} (only P-256/ES256 is supported)",
+                    ec.curve
+                );
+            }
+            let key = DecodingKey::from_ec_components(&ec.x, &ec.y)
+                .context("Invalid EC JWKS key")?;
+            Ok((Algorithm::ES256, key))
+        }
+        other => anyhow::bail!("Unsupported JWKS key type: {other:?}"),
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey};
+use rsa::RsaPrivateKey;
+use std::path::Path;
+
+use crate::config::JwtAlgorithm;
+
+/// Loads (or generates, on first run) the signing/verification keys for
+/// asymmetric JWT modes, so the server never needs to ship a shared
+/// secret to API consumers — only the public key.
+pub struct JwtKeyManager {
+    pub algorithm: Algorithm,
+    pub encoding_key: EncodingKey,
+    pub decoding_key: DecodingKey,
+    /// PEM-encoded public key, safe to hand out to verifiers.
+    pub public_key_pem: Option<String>,
+}
+
+impl JwtKeyManager {
+    pub fn from_config(
+        algorithm: JwtAlgorithm,
+        jwt_secret: &str,
+        private_key_path: Option<&Path>,
+        public_key_path: Option<&Path>,
+    ) -> Result<Self> {
+        match algorithm {
+            JwtAlgorithm::Hs256 => Ok(Self {
+                algorithm: Algorithm::HS256,
+                encoding_key: EncodingKey::from_secret(jwt_secret.as_ref()),
+                decoding_key: DecodingKey::from_secret(jwt_secret.as_ref()),
+                public_key_pem: None,
+            }),
+            JwtAlgorithm::Rs256 => {
+                let (private_pem, public_pem) = load_or_generate_rsa(private_key_path, public_key_path)?;
+                Ok(Self {
+                    algorithm: Algorithm::RS256,
+                    encoding_key: EncodingKey::from_rsa_pem(private_pem.as_bytes())
+                        .context("Invalid RSA private key")?,
+                    decoding_key: DecodingKey::from_rsa_pem(public_pem.as_bytes())
+                        .context("Invalid RSA public key")?,
+                    public_key_pem: Some(public_pem),
+                })
+            }
+            JwtAlgorithm::EdDsa => {
+                let (private_pem, public_pem) = load_or_generate_ed25519(private_key_path, public_key_path)?;
+                Ok(Self {
+                    algorithm: Algorithm::EdDSA,
+                    encoding_key: EncodingKey::from_ed_pem(private_pem.as_bytes())
+                        .context("Invalid Ed25519 private key")?,
+                    decoding_key: DecodingKey::from_ed_pem(public_pem.as_bytes())
+                        .context("Invalid Ed25519 public key")?,
+                    public_key_pem: Some(public_pem),
+                })
+            }
+        }
+    }
+}
+
+fn load_or_generate_rsa(
+    private_key_path: Option<&Path>,
+    public_key_path: Option<&Path>,
+) -> Result<(String, String)> {
+    let (Some(private_path), Some(public_path)) = (private_key_path, public_key_path) else {
+        return bail_missing_key_paths();
+    };
+
+    if private_path.exists() && public_path.exists() {
+        let private_pem = std::fs::read_to_string(private_path)
+            .context("Failed to read RSA private key")?;
+        let public_pem = std::fs::read_to_string(public_path)
+            .context("Failed to read RSA public key")?;
+        return Ok((private_pem, public_pem));
+    }
+
+    let mut rng = rand::thread_rng();
+    let private_key = RsaPrivateKey::new(&mut rng, 2048).context("Failed to generate RSA keypair")?;
+    let public_key = private_key.to_public_key();
+
+    let private_pem = private_key
+        .to_pkcs8_pem(Default::default())
+        .context("Failed to encode RSA private key")?
+        .to_string();
+    let public_pem = public_key
+        .to_public_key_pem(Default::default())
+        .context("Failed to encode RSA public key")?;
+
+    std::fs::write(private_path, &private_pem).context("Failed to write RSA private key")?;
+    std::fs::write(public_path, &public_pem).context("Failed to write RSA public key")?;
+
+    Ok((private_pem, public_pem))
+}
+
+fn load_or_generate_ed25519(
+    private_key_path: Option<&Path>,
+    public_key_path: Option<&Path>,
+) -> Result<(String, String)> {
+    let (Some(private_path), Some(public_path)) = (private_key_path, public_key_path) else {
+        return bail_missing_key_paths();
+    };
+
+    if private_path.exists() && public_path.exists() {
+        let private_pem = std::fs::read_to_string(private_path)
+            .context("Failed to read Ed25519 private key")?;
+        let public_pem = std::fs::read_to_string(public_path)
+            .context("Failed to read Ed25519 public key")?;
+        return Ok((private_pem, public_pem));
+    }
+
+    let keypair = ed25519_dalek::SigningKey::generate(&mut rand::rngs::OsRng);
+    let private_pem = keypair
+        .to_pkcs8_pem(Default::default())
+        .context("Failed to encode Ed25519 private key")?
+        .to_string();
+    let public_pem = keypair
+        .verifying_key()
+        .to_public_key_pem(Default::default())
+        .context("Failed to encode Ed25519 public key")?;
+
+    std::fs::write(private_path, &private_pem).context("Failed to write Ed25519 private key")?;
+    std::fs::write(public_path, &public_pem).context("Failed to write Ed25519 public key")?;
+
+    Ok((private_pem, public_pem))
+}
+
+fn bail_missing_key_paths() -> Result<(String, String)> {
+    anyhow::bail!("jwt_private_key_path and jwt_public_key_path must both be set for asymmetric JWT modes")
+}
@@ -1,7 +1,65 @@
-use actix_web::{web, App, HttpServer, HttpResponse, Result, middleware::Logger};
+use actix_cors::Cors;
+use actix_web::{web, App, HttpServer, HttpResponse, Result, middleware::{Compress, Logger}};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use anyhow::anyhow;
+use std::sync::Arc;
+use anyhow::{anyhow, Context};
+
+mod ws;
+mod config;
+mod compliance_signing;
+mod jwt;
+mod jwks;
+mod middleware;
+
+use config::ServerConfig;
+use jwks::JwksKeyStore;
+use jwt::JwtKeyManager;
+use middleware::auth::AuthMiddleware;
+
+async fn health_handler() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(ApiResponse::success(json!({ "status": "ok" }))))
+}
+
+/// Builds the `AuthMiddleware` this server's routes run behind: a JWKS
+/// endpoint when configured (keys selected per-token by `kid`, refreshed
+/// on `jwt_jwks_refresh_interval`), otherwise the fixed algorithm/key
+/// `JwtKeyManager` loaded (or generated, for RS256/EdDSA) from `config`.
+fn build_auth_middleware(config: &ServerConfig) -> anyhow::Result<AuthMiddleware> {
+    if let Some(jwks_url) = &config.jwt_jwks_url {
+        let jwks = Arc::new(JwksKeyStore::new(jwks_url.clone(), config.jwt_jwks_refresh_interval));
+        return Ok(AuthMiddleware::new_with_jwks(jwks));
+    }
+
+    let keys = JwtKeyManager::from_config(
+        config.jwt_algorithm,
+        &config.jwt_secret,
+        config.jwt_private_key_path.as_deref(),
+        config.jwt_public_key_path.as_deref(),
+    )
+    .context("Failed to load JWT signing/verification keys")?;
+
+    Ok(AuthMiddleware::new_with_algorithm(keys.algorithm, keys.decoding_key))
+}
+
+/// Builds the CORS middleware from `cors_origins`: `["*"]` (the default)
+/// permits any origin, otherwise only the configured origins are allowed.
+fn build_cors(cors_origins: &[String]) -> Cors {
+    let allow_any_origin = cors_origins.iter().any(|origin| origin == "*");
+
+    let mut cors = Cors::default()
+        .allowed_methods(vec!["GET", "POST"])
+        .allow_any_header()
+        .max_age(3600);
+
+    cors = if allow_any_origin {
+        cors.allow_any_origin()
+    } else {
+        cors_origins.iter().fold(cors, |cors, origin| cors.allowed_origin(origin))
+    };
+
+    cors
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanRequest {
@@ -52,7 +110,10 @@ impl<T> ApiResponse<T> {
 }
 
 // Simple handlers without complex middleware
-async fn scan_handler(req: web::Json<ScanRequest>) -> Result<HttpResponse> {
+async fn scan_handler(
+    req: web::Json<ScanRequest>,
+    header_scanner: web::Data<scorton_security::headers::HeaderScanner>,
+) -> Result<HttpResponse> {
     let scanner = scorton_security::SecurityScanner::default();
     
     match req.tool.as_str() {
@@ -115,7 +176,7 @@ async fn scan_handler(req: web::Json<ScanRequest>) -> Result<HttpResponse> {
                 format!("https://{}", req.target)
             };
             
-            match scanner.check_headers(&url).await {
+            match header_scanner.check(&url).await {
                 Ok(headers) => {
                     let response = ApiResponse::success(json!({
                         "tool": req.tool,
@@ -138,18 +199,33 @@ async fn scan_handler(req: web::Json<ScanRequest>) -> Result<HttpResponse> {
     }
 }
 
-async fn compliance_handler(req: web::Json<ComplianceRequest>) -> Result<HttpResponse> {
-    let framework = match req.framework.as_str() {
-        "dora" => scorton_compliance::ComplianceFramework::DORA,
-        "nis2" => scorton_compliance::ComplianceFramework::NIS2,
-        "both" => scorton_compliance::ComplianceFramework::Both,
+async fn compliance_handler(
+    req: web::Json<ComplianceRequest>,
+    assessor: web::Data<Arc<scorton_security::compliance::ComplianceAssessor>>,
+) -> Result<HttpResponse> {
+    let assessment = match req.framework.as_str() {
+        "dora" => assessor
+            .assess_dora_compliance(&req.target)
+            .await
+            .map(|result| json!({ "dora": result })),
+        "nis2" => assessor
+            .assess_nis2_compliance(&req.target)
+            .await
+            .map(|result| json!({ "nis2": result })),
+        "both" => match (
+            assessor.assess_dora_compliance(&req.target).await,
+            assessor.assess_nis2_compliance(&req.target).await,
+        ) {
+            (Ok(dora), Ok(nis2)) => Ok(json!({ "dora": dora, "nis2": nis2 })),
+            (Err(e), _) | (_, Err(e)) => Err(e),
+        },
         _ => {
             let response = ApiResponse::<()>::error(format!("Unknown framework: {}", req.framework));
             return Ok(HttpResponse::BadRequest().json(response));
         }
     };
 
-    match scorton_compliance::run_compliance_assessment(&req.framework, &req.target).await {
+    match assessment {
         Ok(result) => {
             let response = ApiResponse::success(json!({
                 "framework": req.framework,
@@ -166,6 +242,55 @@ async fn compliance_handler(req: web::Json<ComplianceRequest>) -> Result<HttpRes
     }
 }
 
+/// Renders the shared `ComplianceMetrics` registry in the Prometheus text
+/// exposition format, so assessments `compliance_handler` runs (one-shot
+/// or [`scorton_security::compliance::AssessmentMode::Continuous`]) can
+/// be scraped and alerted on over time.
+async fn metrics_handler(
+    metrics: web::Data<Arc<scorton_security::metrics::ComplianceMetrics>>,
+) -> Result<HttpResponse> {
+    match metrics.render() {
+        Ok(body) => Ok(HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4")
+            .body(body)),
+        Err(e) => {
+            let response = ApiResponse::<()>::error(format!("Failed to render metrics: {}", e));
+            Ok(HttpResponse::InternalServerError().json(response))
+        }
+    }
+}
+
+/// Runs a DORA/NIS2 assessment through `scorton_compliance::ComplianceEngine`
+/// (its default configuration, not the `ComplianceAssessor` that
+/// [`compliance_handler`] uses) and signs the report with this server's
+/// Schnorr attestation key, so the caller gets a tamper-evident report
+/// instead of a bare JSON blob.
+async fn compliance_attested_handler(
+    req: web::Json<ComplianceRequest>,
+    signing_key: web::Data<k256::schnorr::SigningKey>,
+) -> Result<HttpResponse> {
+    let dora_enabled = matches!(req.framework.as_str(), "dora" | "both");
+    let nis2_enabled = matches!(req.framework.as_str(), "nis2" | "both");
+    if !dora_enabled && !nis2_enabled {
+        let response = ApiResponse::<()>::error(format!("Unknown framework: {}", req.framework));
+        return Ok(HttpResponse::BadRequest().json(response));
+    }
+
+    let engine = scorton_compliance::ComplianceEngine::new(scorton_compliance::ComplianceConfig {
+        dora_enabled,
+        nis2_enabled,
+        ..Default::default()
+    });
+
+    match engine.run_and_sign_assessment(&req.target, &signing_key).await {
+        Ok(attested) => Ok(HttpResponse::Ok().json(ApiResponse::success(attested))),
+        Err(e) => {
+            let response = ApiResponse::<()>::error(format!("Signed compliance assessment failed: {}", e));
+            Ok(HttpResponse::InternalServerError().json(response))
+        }
+    }
+}
+
 async fn score_handler(req: web::Json<ScoreRequest>) -> Result<HttpResponse> {
     // Placeholder implementation for cyber score calculation
     let technical = 0.75;
@@ -195,21 +320,90 @@ async fn calculate_cyber_score(_target: &str) -> HashMap<String, f64> {
     scores
 }
 
+/// Command-line overrides for settings `ServerConfig::from_env` otherwise
+/// reads from the environment, so an operator can flip assessment depth
+/// for a single run without touching `SCORTON_ASSESSMENT_MODE`.
+#[derive(clap::Parser, Debug)]
+#[command(about = "ScortonJS Rust API server")]
+struct Cli {
+    /// Overrides SCORTON_ASSESSMENT_MODE: one_shot, continuous, or deep_scan.
+    #[arg(long, value_name = "MODE")]
+    assessment_mode: Option<String>,
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    println!("Starting ScortonJS Rust API Server on 127.0.0.1:8001");
-    
-    HttpServer::new(|| {
+    let cli = <Cli as clap::Parser>::parse();
+
+    let mut config = ServerConfig::from_env()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    if let Some(mode) = cli.assessment_mode.as_deref() {
+        config.assessment_mode = scorton_security::compliance::AssessmentMode::from_env_str(mode)
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid --assessment-mode: {}", mode),
+                )
+            })?;
+    }
+    let bind_addr = format!("{}:{}", config.host, config.port);
+
+    println!("Starting ScortonJS Rust API Server on {}", bind_addr);
+
+    let auth_middleware = build_auth_middleware(&config)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    let cors_origins = config.cors_origins.clone();
+    let header_scanner = web::Data::new(scorton_security::headers::HeaderScanner::new());
+    let compliance_signing_key = web::Data::new(
+        compliance_signing::load_or_generate_signing_key(config.compliance_signing_key_path.as_deref())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?,
+    );
+    let compliance_metrics = Arc::new(
+        scorton_security::metrics::ComplianceMetrics::new()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?,
+    );
+    let compliance_assessor = web::Data::new(Arc::new(
+        scorton_security::compliance::ComplianceAssessor::with_metrics(
+            scorton_security::compliance::ComplianceConfig {
+                mode: config.assessment_mode,
+                ..Default::default()
+            },
+            compliance_metrics.clone(),
+        ),
+    ));
+    let compliance_metrics = web::Data::new(compliance_metrics);
+
+    HttpServer::new(move || {
         App::new()
+            .app_data(header_scanner.clone())
+            .app_data(compliance_signing_key.clone())
+            .app_data(compliance_metrics.clone())
+            .app_data(compliance_assessor.clone())
             .wrap(Logger::default())
+            .wrap(Compress::default())
+            .wrap(auth_middleware.clone())
+            // Registered last so it runs first (actix-web executes wrapped
+            // middleware in reverse registration order), letting CORS
+            // preflight (`OPTIONS`) requests short-circuit before the auth
+            // middleware ever sees them.
+            .wrap(build_cors(&cors_origins))
+            .route("/api/health", web::get().to(health_handler))
+            .route("/metrics", web::get().to(metrics_handler))
             .service(
                 web::scope("/api")
                     .route("/scan", web::post().to(scan_handler))
                     .route("/compliance", web::post().to(compliance_handler))
+                    .route("/compliance/attested", web::post().to(compliance_attested_handler))
                     .route("/score", web::post().to(score_handler))
             )
+            .service(
+                web::scope("/ws")
+                    .route("/scan", web::get().to(ws::scan_progress))
+                    .route("/port-scan", web::get().to(ws::port_scan_progress))
+                    .route("/dns-enum", web::get().to(ws::dns_enum_progress))
+            )
     })
-    .bind("127.0.0.1:8001")?
+    .bind(bind_addr)?
     .run()
     .await
 }
\ No newline at end of file
@@ -1,10 +1,16 @@
 use actix_web::{HttpRequest, HttpResponse, Error, dev::ServiceRequest, dev::ServiceResponse};
+use actix_web::http::header;
 use actix_web::middleware::ServiceRequestExt;
-use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::Arc;
 use actix_web::dev::{forward_ready, Service, Transform};
 
+use crate::jwks::JwksKeyStore;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,
@@ -12,13 +18,55 @@ pub struct Claims {
     pub iat: usize,
 }
 
+/// Where `AuthMiddleware` gets the key to verify a token against: either
+/// a fixed algorithm/key picked at startup, or a JWKS endpoint whose
+/// published keys are looked up by the token's `kid` header and cached
+/// (see [`JwksKeyStore`]), so issuer-side key rotation doesn't require
+/// restarting the server.
+#[derive(Clone)]
+pub enum KeySource {
+    Static {
+        algorithm: Algorithm,
+        decoding_key: DecodingKey,
+    },
+    Jwks(Arc<JwksKeyStore>),
+}
+
+#[derive(Clone)]
 pub struct AuthMiddleware {
-    jwt_secret: String,
+    key_source: KeySource,
 }
 
 impl AuthMiddleware {
+    /// HS256 with a static shared secret, kept for backward compatibility.
     pub fn new(jwt_secret: String) -> Self {
-        Self { jwt_secret }
+        Self {
+            key_source: KeySource::Static {
+                algorithm: Algorithm::HS256,
+                decoding_key: DecodingKey::from_secret(jwt_secret.as_ref()),
+            },
+        }
+    }
+
+    /// Validates with whichever algorithm/key the server was configured
+    /// for (HS256 with a shared secret, or RS256/EdDSA with the public
+    /// half of a generated keypair).
+    pub fn new_with_algorithm(algorithm: Algorithm, decoding_key: DecodingKey) -> Self {
+        Self {
+            key_source: KeySource::Static {
+                algorithm,
+                decoding_key,
+            },
+        }
+    }
+
+    /// Validates against a JWKS endpoint's published keys instead of a
+    /// fixed key, selecting the right one per-request by the token's
+    /// `kid` header.
+    pub fn new_with_jwks(jwks: Arc<JwksKeyStore>) -> Self {
+        Self {
+            key_source: KeySource::Jwks(jwks),
+        }
     }
 }
 
@@ -36,15 +84,77 @@ where
 
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(AuthMiddlewareService {
-            service,
-            jwt_secret: self.jwt_secret.clone(),
+            service: Rc::new(service),
+            key_source: self.key_source.clone(),
         }))
     }
 }
 
 pub struct AuthMiddlewareService<S> {
-    service: S,
-    jwt_secret: String,
+    service: Rc<S>,
+    key_source: KeySource,
+}
+
+/// A structured 401 response so API consumers can branch on `error`
+/// without scraping a human-readable string.
+fn unauthorized(reason: &str) -> HttpResponse {
+    HttpResponse::Unauthorized().json(serde_json::json!({
+        "success": false,
+        "error": reason,
+        "timestamp": chrono::Utc::now()
+    }))
+}
+
+/// True for a WebSocket handshake request (`Connection: Upgrade` +
+/// `Upgrade: websocket`).
+fn is_websocket_upgrade(req: &ServiceRequest) -> bool {
+    let upgrades_to_websocket = req
+        .headers()
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false);
+
+    let connection_requests_upgrade = req
+        .headers()
+        .get(header::CONNECTION)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_ascii_lowercase().contains("upgrade"))
+        .unwrap_or(false);
+
+    upgrades_to_websocket && connection_requests_upgrade
+}
+
+/// Extracts the bearer token for this request: the `Authorization`
+/// header for ordinary requests, or — since browsers can't set
+/// `Authorization` on a WebSocket handshake — the `access_token` query
+/// parameter or the `Sec-WebSocket-Protocol` header for an upgrade
+/// request.
+fn extract_token(req: &ServiceRequest) -> Option<String> {
+    if let Some(token) = req
+        .headers()
+        .get("Authorization")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header_str| header_str.strip_prefix("Bearer "))
+    {
+        return Some(token.to_string());
+    }
+
+    if !is_websocket_upgrade(req) {
+        return None;
+    }
+
+    if let Ok(query) = actix_web::web::Query::<HashMap<String, String>>::from_query(req.query_string()) {
+        if let Some(token) = query.get("access_token") {
+            return Some(token.clone());
+        }
+    }
+
+    req.headers()
+        .get("Sec-WebSocket-Protocol")
+        .and_then(|header| header.to_str().ok())
+        .map(|protocols| protocols.split(',').next().unwrap_or("").trim().to_string())
+        .filter(|token| !token.is_empty())
 }
 
 impl<S, B> Service<ServiceRequest> for AuthMiddlewareService<S>
@@ -62,71 +172,42 @@ where
     fn call(&self, req: ServiceRequest) -> Self::Future {
         // Skip auth for health check
         if req.path() == "/api/health" {
-            return Box::pin(self.service.call(req));
+            let service = self.service.clone();
+            return Box::pin(async move { service.call(req).await });
         }
 
-        let auth_header = req.headers().get("Authorization");
-        let jwt_secret = self.jwt_secret.clone();
-
-        match auth_header {
-            Some(header) => {
-                if let Ok(header_str) = header.to_str() {
-                    if header_str.starts_with("Bearer ") {
-                        let token = &header_str[7..];
-                        let validation = Validation::new(Algorithm::HS256);
-                        
-                        match decode::<Claims>(token, &DecodingKey::from_secret(jwt_secret.as_ref()), &validation) {
-                            Ok(_claims) => {
-                                // Token is valid, continue with the request
-                                Box::pin(self.service.call(req))
-                            }
-                            Err(_) => {
-                                // Token is invalid
-                                Box::pin(async move {
-                                    Ok(req.into_response(
-                                        HttpResponse::Unauthorized().json(serde_json::json!({
-                                            "success": false,
-                                            "error": "Invalid token",
-                                            "timestamp": chrono::Utc::now()
-                                        }))
-                                    ))
-                                })
-                            }
-                        }
-                    } else {
-                        Box::pin(async move {
-                            Ok(req.into_response(
-                                HttpResponse::Unauthorized().json(serde_json::json!({
-                                    "success": false,
-                                    "error": "Invalid authorization header format",
-                                    "timestamp": chrono::Utc::now()
-                                }))
-                            ))
-                        })
+        let token = extract_token(&req);
+
+        let key_source = self.key_source.clone();
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let Some(token) = token else {
+                return Ok(req.into_response(unauthorized("Missing or malformed authorization header")));
+            };
+
+            let (algorithm, decoding_key) = match &key_source {
+                KeySource::Static {
+                    algorithm,
+                    decoding_key,
+                } => (*algorithm, decoding_key.clone()),
+                KeySource::Jwks(jwks) => {
+                    let kid = match decode_header(&token).ok().and_then(|header| header.kid) {
+                        Some(kid) => kid,
+                        None => return Ok(req.into_response(unauthorized("Token is missing a key id (kid)"))),
+                    };
+                    match jwks.get_key(&kid).await {
+                        Ok(key) => key,
+                        Err(_) => return Ok(req.into_response(unauthorized("Unknown or unavailable signing key"))),
                     }
-                } else {
-                    Box::pin(async move {
-                        Ok(req.into_response(
-                            HttpResponse::Unauthorized().json(serde_json::json!({
-                                "success": false,
-                                "error": "Invalid authorization header",
-                                "timestamp": chrono::Utc::now()
-                            }))
-                        ))
-                    })
                 }
+            };
+
+            let validation = Validation::new(algorithm);
+            match decode::<Claims>(&token, &decoding_key, &validation) {
+                Ok(_claims) => service.call(req).await,
+                Err(_) => Ok(req.into_response(unauthorized("Invalid token"))),
             }
-            None => {
-                Box::pin(async move {
-                    Ok(req.into_response(
-                        HttpResponse::Unauthorized().json(serde_json::json!({
-                            "success": false,
-                            "error": "Missing authorization header",
-                            "timestamp": chrono::Utc::now()
-                        }))
-                    ))
-                })
-            }
-        }
+        })
     }
 }
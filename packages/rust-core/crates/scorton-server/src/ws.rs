@@ -0,0 +1,203 @@
+use actix::{Actor, ActorContext, AsyncContext, StreamHandler};
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use actix_web_actors::ws;
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+
+type ProgressFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+struct ScanFrame(String);
+
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+struct ScanComplete;
+
+/// The shared actor machinery behind every streaming WS endpoint below:
+/// spawns a caller-supplied task that emits one JSON frame per event over
+/// a broadcast channel, relays each frame to the WS client as a text
+/// message, and closes the socket once the task completes. `run` owns
+/// whatever scanner/orchestrator call actually produces the events —
+/// this struct only knows how to relay already-serialized frames.
+pub struct ProgressSocket {
+    run: Option<Box<dyn FnOnce(tokio::sync::broadcast::Sender<String>) -> ProgressFuture + Send>>,
+}
+
+impl ProgressSocket {
+    fn new<F, Fut>(run: F) -> Self
+    where
+        F: FnOnce(tokio::sync::broadcast::Sender<String>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            run: Some(Box::new(move |sender| Box::pin(run(sender)))),
+        }
+    }
+}
+
+impl Actor for ProgressSocket {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        let addr = ctx.address();
+        let Some(run) = self.run.take() else { return };
+
+        actix::spawn(async move {
+            let (sender, mut receiver) = tokio::sync::broadcast::channel(32);
+            let task = tokio::spawn(run(sender));
+
+            while let Ok(frame) = receiver.recv().await {
+                addr.do_send(ScanFrame(frame));
+            }
+
+            let _ = task.await;
+            addr.do_send(ScanComplete);
+        });
+    }
+}
+
+impl actix::Handler<ScanFrame> for ProgressSocket {
+    type Result = ();
+
+    fn handle(&mut self, msg: ScanFrame, ctx: &mut Self::Context) {
+        ctx.text(msg.0);
+    }
+}
+
+impl actix::Handler<ScanComplete> for ProgressSocket {
+    type Result = ();
+
+    fn handle(&mut self, _msg: ScanComplete, ctx: &mut Self::Context) {
+        ctx.close(None);
+        ctx.stop();
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for ProgressSocket {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScanProgressQuery {
+    pub target: String,
+}
+
+/// `GET /ws/scan?target=<host>` — upgrades to a WebSocket and streams
+/// `ScanEvent` JSON frames for a comprehensive scan of `target`.
+pub async fn scan_progress(
+    req: HttpRequest,
+    stream: web::Payload,
+    query: web::Query<ScanProgressQuery>,
+) -> Result<HttpResponse, Error> {
+    let target = query.target.clone();
+
+    ws::start(
+        ProgressSocket::new(move |sender| async move {
+            let orchestrator = scorton_security::scanner::ScannerOrchestrator::new(
+                scorton_security::scanner::ScannerConfig::default(),
+            );
+            let (events_tx, mut events_rx) = tokio::sync::broadcast::channel(32);
+
+            let scan = tokio::spawn(async move {
+                let _ = orchestrator.run_comprehensive_scan_streaming(&target, events_tx).await;
+            });
+
+            while let Ok(event) = events_rx.recv().await {
+                if let Ok(frame) = serde_json::to_string(&event) {
+                    let _ = sender.send(frame);
+                }
+            }
+
+            let _ = scan.await;
+        }),
+        &req,
+        stream,
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PortScanProgressQuery {
+    pub target: String,
+    /// Comma-separated port list, e.g. `22,80,443`.
+    pub ports: String,
+}
+
+/// `GET /ws/port-scan?target=<host>&ports=22,80,443` — upgrades to a
+/// WebSocket and streams a `PortScanResult` frame per port as its scan
+/// task completes.
+pub async fn port_scan_progress(
+    req: HttpRequest,
+    stream: web::Payload,
+    query: web::Query<PortScanProgressQuery>,
+) -> Result<HttpResponse, Error> {
+    let target = query.target.clone();
+    let ports: Vec<u16> = query
+        .ports
+        .split(',')
+        .filter_map(|p| p.trim().parse::<u16>().ok())
+        .collect();
+
+    ws::start(
+        ProgressSocket::new(move |sender| async move {
+            let scanner = scorton_security::SecurityScanner::default();
+            let (results_tx, mut results_rx) = tokio::sync::broadcast::channel(32);
+
+            let scan = tokio::spawn(async move { scanner.port_scan_streaming(&target, &ports, results_tx).await });
+
+            while let Ok(result) = results_rx.recv().await {
+                if let Ok(frame) = serde_json::to_string(&result) {
+                    let _ = sender.send(frame);
+                }
+            }
+
+            let _ = scan.await;
+        }),
+        &req,
+        stream,
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DnsEnumProgressQuery {
+    pub domain: String,
+}
+
+/// `GET /ws/dns-enum?domain=<domain>` — upgrades to a WebSocket and
+/// streams a `DNSRecord` frame per record type as its lookup completes.
+pub async fn dns_enum_progress(
+    req: HttpRequest,
+    stream: web::Payload,
+    query: web::Query<DnsEnumProgressQuery>,
+) -> Result<HttpResponse, Error> {
+    let domain = query.domain.clone();
+
+    ws::start(
+        ProgressSocket::new(move |sender| async move {
+            let scanner = scorton_security::SecurityScanner::default();
+            let (records_tx, mut records_rx) = tokio::sync::broadcast::channel(32);
+
+            let scan = tokio::spawn(async move { scanner.dns_enum_streaming(&domain, records_tx).await });
+
+            while let Ok(record) = records_rx.recv().await {
+                if let Ok(frame) = serde_json::to_string(&record) {
+                    let _ = sender.send(frame);
+                }
+            }
+
+            let _ = scan.await;
+        }),
+        &req,
+        stream,
+    )
+}